@@ -1,6 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::hash::hashv;
 
-declare_id!("CounciL11111111111111111111111111111111111");
+mod selection;
+
+use selection::{bucket_seed, weighted_sample, weighted_shuffle};
+
+declare_id!("B93My2qx79ow328QoKigQRwGQpb4CgchkwuzpmBHEs2p");
 
 #[program]
 pub mod council_selection {
@@ -8,24 +14,53 @@ pub mod council_selection {
 
     /// Initialize a new council session
     pub fn initialize_session(
+        ctx: Context<InitializeSession>,
+        session_id: String,
+        config: SessionConfig,
+    ) -> Result<()> {
+        init_session(
+            &mut ctx.accounts.session,
+            ctx.accounts.authority.key(),
+            session_id,
+            config,
+        )
+    }
+
+    /// Initialize a session and store its candidate pool in one call, with
+    /// default settings for everything else
+    ///
+    /// `pool_categories` is parallel to `agent_pool`; use `initialize_session`
+    /// for quotas, weights or cooldowns.
+    pub fn initialize_session_with_pool(
         ctx: Context<InitializeSession>,
         session_id: String,
         required_agents: u8,
         diversity_required: bool,
+        agent_pool: Vec<String>,
+        pool_categories: Vec<u8>,
     ) -> Result<()> {
-        let session = &mut ctx.accounts.session;
-        session.session_id = session_id;
-        session.authority = ctx.accounts.authority.key();
-        session.required_agents = required_agents;
-        session.diversity_required = diversity_required;
-        session.selected_agents = Vec::new();
-        session.vrf_seed = 0;
-        session.vrf_fulfilled = false;
-        session.timestamp = Clock::get()?.unix_timestamp;
-        session.status = SessionStatus::Initialized;
-
-        msg!("Council session initialized: {}", session.session_id);
-        Ok(())
+        require!(
+            agent_pool.len() >= required_agents as usize,
+            ErrorCode::PoolTooSmall
+        );
+
+        init_session(
+            &mut ctx.accounts.session,
+            ctx.accounts.authority.key(),
+            session_id,
+            SessionConfig {
+                required_agents,
+                diversity_required,
+                min_distinct_categories: 0,
+                agent_pool,
+                pool_categories,
+                category_quotas: Vec::new(),
+                vrf_timeout_seconds: DEFAULT_VRF_TIMEOUT_SECONDS,
+                service_cooldown_seconds: 0,
+                agent_weights: Vec::new(),
+                allow_deterministic: false,
+            },
+        )
     }
 
     /// Request VRF for agent selection
@@ -40,22 +75,41 @@ pub mod council_selection {
             ErrorCode::InvalidSessionStatus
         );
 
-        session.vrf_seed = vrf_seed;
-        session.status = SessionStatus::VRFRequested;
+        record_vrf_request(session, &ctx.accounts.randomness_account, vrf_seed)
+    }
+
+    /// Replace a VRF request the oracle never answered
+    ///
+    /// Allowed once `vrf_timeout_seconds` have passed since the last request;
+    /// commits a fresh randomness account and seed exactly as `request_vrf` does.
+    pub fn re_request_vrf(
+        ctx: Context<ReRequestVRF>,
+        new_seed: u64,
+    ) -> Result<()> {
+        let session = &mut ctx.accounts.session;
 
-        msg!("VRF requested for session: {}, seed: {}", session.session_id, vrf_seed);
+        require!(
+            session.status == SessionStatus::VRFRequested,
+            ErrorCode::InvalidSessionStatus
+        );
 
-        // In production, this would interact with Chainlink VRF or Pyth Entropy
-        // For now, we mark it as requested
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now.saturating_sub(session.vrf_requested_at) >= session.vrf_timeout_seconds,
+            ErrorCode::VRFRequestNotExpired
+        );
 
-        Ok(())
+        record_vrf_request(session, &ctx.accounts.randomness_account, new_seed)
     }
 
-    /// Fulfill VRF and select agents
+    /// Fulfill VRF from the committed Switchboard On-Demand randomness account
+    ///
+    /// The revealed value is only accepted from the account recorded at
+    /// `request_vrf`, still bound to the same seed slot, and revealed in the
+    /// current slot. It is mixed with the committed `vrf_seed` so the session's
+    /// random number is tied to this request.
     pub fn fulfill_vrf(
         ctx: Context<FulfillVRF>,
-        random_number: u64,
-        vrf_proof: Vec<u8>,
     ) -> Result<()> {
         let session = &mut ctx.accounts.session;
 
@@ -64,8 +118,25 @@ pub mod council_selection {
             ErrorCode::InvalidSessionStatus
         );
 
-        // Verify VRF proof (simplified for demonstration)
-        require!(vrf_proof.len() > 0, ErrorCode::InvalidVRFProof);
+        require_keys_eq!(
+            ctx.accounts.randomness_account.key(),
+            session.randomness_account,
+            ErrorCode::StaleRandomness
+        );
+
+        let randomness = SwitchboardRandomness::parse(&ctx.accounts.randomness_account)?;
+        let clock = Clock::get()?;
+        require!(
+            randomness.seed_slot == session.randomness_seed_slot
+                && randomness.reveal_slot == clock.slot,
+            ErrorCode::StaleRandomness
+        );
+
+        let digest = hashv(&[&randomness.value, &session.vrf_seed.to_le_bytes()]).to_bytes();
+        let mut random_bytes = [0u8; 8];
+        random_bytes.copy_from_slice(&digest[..8]);
+        let random_number = u64::from_le_bytes(random_bytes);
+        let vrf_proof = randomness.value.to_vec();
 
         session.vrf_fulfilled = true;
         session.random_number = random_number;
@@ -77,32 +148,400 @@ pub mod council_selection {
         Ok(())
     }
 
-    /// Select agents using the VRF random number
-    pub fn select_agents(
-        ctx: Context<SelectAgents>,
+    /// Pre-flight check that the session was committed to `expected_seed`,
+    /// so an integrator does not fulfill randomness for the wrong session
+    pub fn verify_vrf_seed(
+        ctx: Context<VerifyVRFSeed>,
+        expected_seed: u64,
+    ) -> Result<()> {
+        let session = &ctx.accounts.session;
+
+        require!(
+            session.vrf_seed == expected_seed,
+            ErrorCode::VRFSeedMismatch
+        );
+
+        Ok(())
+    }
+
+    /// Seat a hand-picked council without VRF, for local tests and demos
+    ///
+    /// Only sessions created with `allow_deterministic` accept this. Every
+    /// agent must come from the committed pool, which supplies its category.
+    /// The session is marked `deterministic`, and verification reports that
+    /// the selection is not VRF-backed.
+    pub fn select_agents_deterministic(
+        ctx: Context<SelectAgentsDeterministic>,
         agent_ids: Vec<String>,
     ) -> Result<()> {
         let session = &mut ctx.accounts.session;
 
+        require!(
+            session.allow_deterministic,
+            ErrorCode::DeterministicSelectionDisabled
+        );
+
+        require!(
+            session.status == SessionStatus::Initialized,
+            ErrorCode::InvalidSessionStatus
+        );
+
+        let agent_categories = agent_ids
+            .iter()
+            .map(|id| {
+                session
+                    .agent_pool
+                    .iter()
+                    .position(|a| a == id)
+                    .map(|i| session.pool_categories[i])
+                    .ok_or(ErrorCode::AgentNotInPool.into())
+            })
+            .collect::<Result<Vec<u8>>>()?;
+
+        session.deterministic = true;
+        seat_agents(session, agent_ids, agent_categories, ctx.remaining_accounts)
+    }
+
+    /// Derive the council from the VRF output and the committed agent pool
+    ///
+    /// Walks the pool in an order seeded by `random_number` (Fisher-Yates, or
+    /// stake-weighted sampling when `agent_weights` are set), first
+    /// seating one candidate from each new category until
+    /// `min_distinct_categories` are represented, then filling the remaining
+    /// seats in the same order. With category quotas, each
+    /// category is shuffled separately and contributes exactly its quota.
+    /// Anyone can recompute the same selection from the stored random number
+    /// and pool.
+    ///
+    /// Agents still within `service_cooldown_seconds` of their last service
+    /// are skipped; their `AgentProfile`s are passed as remaining accounts,
+    /// parallel to `agent_pool`.
+    pub fn derive_selection(
+        ctx: Context<DeriveSelection>,
+    ) -> Result<()> {
+        let session = &mut ctx.accounts.session;
+
         require!(
             session.status == SessionStatus::VRFFulfilled,
             ErrorCode::InvalidSessionStatus
         );
 
+        let now = Clock::get()?.unix_timestamp;
+        let records = ctx.remaining_accounts;
+        let eligible = service_eligibility(
+            records,
+            &session.agent_pool,
+            session.service_cooldown_seconds,
+            now,
+        )?;
         require!(
-            agent_ids.len() == session.required_agents as usize,
-            ErrorCode::InvalidAgentCount
+            eligible.iter().filter(|&&e| e).count() >= session.required_agents as usize,
+            ErrorCode::AgentOnCooldown
         );
 
-        session.selected_agents = agent_ids.clone();
+        // Pool indices of the seated candidates, in the order they were picked
+        let picks = session.pick_indices(&eligible)?;
+        session.cooldown_skipped = eligible
+            .iter()
+            .enumerate()
+            .filter(|(_, &e)| !e)
+            .fold(0, |mask, (i, _)| mask | 1 << i);
+
+        let selected_agents: Vec<String> =
+            picks.iter().map(|&i| session.agent_pool[i].clone()).collect();
+        record_service(records, &session.agent_pool, &selected_agents, now)?;
+
+        session.selection_log = picks
+            .iter()
+            .enumerate()
+            .map(|(slot, &index)| SelectionStep {
+                slot: slot as u8,
+                pool_index: index as u8,
+                agent_id: session.agent_pool[index].clone(),
+            })
+            .collect();
+        session.agent_categories = picks.iter().map(|&i| session.pool_categories[i]).collect();
+        session.selected_agents = selected_agents;
         session.status = SessionStatus::AgentsSelected;
-        session.selection_timestamp = Clock::get()?.unix_timestamp;
+        session.selection_timestamp = now;
+
+        msg!(
+            "Agents derived for session: {}, count: {}",
+            session.session_id,
+            session.selected_agents.len()
+        );
+
+        Ok(())
+    }
+
+    /// Swap a selected agent for another candidate from the committed pool,
+    /// e.g. when the agent is found compromised before finalization
+    ///
+    /// The swap is recorded in `replacements`; `selection_log` keeps the
+    /// original draw. The new council must still satisfy the diversity and
+    /// quota requirements.
+    pub fn replace_agent(
+        ctx: Context<ReplaceAgent>,
+        index: u8,
+        replacement: String,
+    ) -> Result<()> {
+        let session = &mut ctx.accounts.session;
+
+        require!(
+            session.status == SessionStatus::AgentsSelected,
+            ErrorCode::InvalidSessionStatus
+        );
+
+        require!(
+            (index as usize) < session.selected_agents.len(),
+            ErrorCode::InvalidReplacement
+        );
+
+        require!(
+            session.replacements.len() < MAX_REPLACEMENTS,
+            ErrorCode::ReplacementsFull
+        );
+
+        let pool_index = session
+            .agent_pool
+            .iter()
+            .position(|a| *a == replacement)
+            .ok_or(ErrorCode::InvalidReplacement)?;
+
+        require!(
+            !session.selected_agents.contains(&replacement),
+            ErrorCode::DuplicateAgent
+        );
+
+        let mut categories = session.agent_categories.clone();
+        categories[index as usize] = session.pool_categories[pool_index];
+        require!(
+            distinct_category_count(&categories) >= session.min_distinct_categories as usize,
+            ErrorCode::DiversityViolation
+        );
+
+        require!(
+            session.quotas_met(&categories),
+            ErrorCode::QuotaMismatch
+        );
+
+        let removed = std::mem::replace(&mut session.selected_agents[index as usize], replacement.clone());
+        session.agent_categories = categories;
+        session.replacements.push(Replacement {
+            index,
+            removed: removed.clone(),
+            replacement,
+        });
+
+        msg!(
+            "Agent {} replaced by {} in session: {}",
+            removed,
+            session.selected_agents[index as usize],
+            session.session_id
+        );
+        Ok(())
+    }
+
+    /// Create the program-wide `AgentRegistry`, naming the authority that
+    /// registers agents and sets their reputation
+    ///
+    /// Only the program's upgrade authority may create it, so the first
+    /// caller cannot claim control over every agent's reputation.
+    pub fn initialize_agent_registry(
+        ctx: Context<InitializeAgentRegistry>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.program_data.upgrade_authority_address == Some(ctx.accounts.authority.key()),
+            ErrorCode::NotUpgradeAuthority
+        );
+
+        let registry = &mut ctx.accounts.registry;
+        registry.authority = ctx.accounts.authority.key();
+
+        msg!("Agent registry initialized, authority: {}", registry.authority);
+        Ok(())
+    }
+
+    /// Create the profile an agent is known by across sessions and debates,
+    /// at neutral reputation. Registry authority only.
+    pub fn register_agent(
+        ctx: Context<RegisterAgent>,
+        agent_id: String,
+        pubkey: Pubkey,
+        category: u8,
+    ) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        profile.set_inner(AgentProfile::new(agent_id, pubkey, category)?);
+
+        msg!("Agent registered: {}, category: {}", profile.agent_id, category);
+        Ok(())
+    }
+
+    /// Change the key an agent signs with and its category. Registry
+    /// authority only.
+    pub fn update_agent_profile(
+        ctx: Context<UpdateAgentProfile>,
+        _agent_id: String,
+        pubkey: Pubkey,
+        category: u8,
+    ) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        profile.pubkey = pubkey;
+        profile.category = category;
+
+        msg!("Agent profile updated: {}, category: {}", profile.agent_id, category);
+        Ok(())
+    }
+
+    /// Set an agent's reputation, at most `MAX_REPUTATION`. Registry
+    /// authority only.
+    pub fn set_agent_reputation(
+        ctx: Context<UpdateAgentProfile>,
+        _agent_id: String,
+        reputation: u16,
+    ) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        profile.set_reputation(reputation)?;
+
+        msg!("Agent reputation set: {}, reputation: {}", profile.agent_id, reputation);
+        Ok(())
+    }
+
+    /// Whether an agent would be off the session's service cooldown at `at`,
+    /// so schedulers can pre-filter a pool before `derive_selection`
+    pub fn is_eligible(
+        ctx: Context<IsEligible>,
+        _agent_id: String,
+        at: i64,
+    ) -> Result<bool> {
+        Ok(ctx
+            .accounts
+            .profile
+            .is_eligible(ctx.accounts.session.service_cooldown_seconds, at))
+    }
+
+    /// Abort a session that can no longer complete, e.g. an unanswered VRF request
+    pub fn cancel_session(
+        ctx: Context<CancelSession>,
+    ) -> Result<()> {
+        let session = &mut ctx.accounts.session;
+
+        require!(
+            session.status != SessionStatus::Completed
+                && session.status != SessionStatus::Cancelled,
+            ErrorCode::InvalidSessionStatus
+        );
+
+        session.status = SessionStatus::Cancelled;
+
+        msg!("Council session cancelled: {}", session.session_id);
+        Ok(())
+    }
+
+    /// Close out a verified selection
+    pub fn finalize_session(
+        ctx: Context<FinalizeSession>,
+    ) -> Result<()> {
+        let session = &mut ctx.accounts.session;
+
+        require!(
+            session.status == SessionStatus::AgentsSelected,
+            ErrorCode::InvalidSessionStatus
+        );
+
+        require!(
+            session.selection_is_valid(),
+            ErrorCode::VerificationFailed
+        );
+
+        session.status = SessionStatus::Completed;
+        session.completion_timestamp = Clock::get()?.unix_timestamp;
+
+        let stats = &mut ctx.accounts.stats;
+        stats.sessions_finalized = stats.sessions_finalized.saturating_add(1);
+        if distinct_category_count(&session.agent_categories) >= session.required_agents as usize {
+            stats.diverse_sessions = stats.diverse_sessions.saturating_add(1);
+        }
+        stats.total_pool_size = stats.total_pool_size.saturating_add(session.agent_pool.len() as u64);
+
+        msg!("Council session finalized: {}", session.session_id);
+        Ok(())
+    }
+
+    /// Create the program-wide `SelectionStats` account ahead of time;
+    /// `finalize_session` also creates it on first use
+    pub fn initialize_selection_stats(
+        ctx: Context<InitializeSelectionStats>,
+    ) -> Result<()> {
+        let stats = &mut ctx.accounts.stats;
+        stats.sessions_finalized = 0;
+        stats.diverse_sessions = 0;
+        stats.total_pool_size = 0;
+
+        msg!("Selection stats initialized");
+        Ok(())
+    }
+
+    /// Read the running totals across every finalized session
+    pub fn get_selection_stats(
+        ctx: Context<GetSelectionStats>,
+    ) -> Result<SelectionStatsSummary> {
+        let stats = &ctx.accounts.stats;
+
+        Ok(SelectionStatsSummary {
+            sessions_finalized: stats.sessions_finalized,
+            diverse_sessions: stats.diverse_sessions,
+            average_pool_size: stats.average_pool_size(),
+        })
+    }
+
+    /// Start handing control of the session to `new_authority`, which must
+    /// confirm with `accept_authority`
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let session = &mut ctx.accounts.session;
+        session.pending_authority = Some(new_authority);
+
+        msg!("Authority transfer proposed to {}", new_authority);
+        Ok(())
+    }
+
+    /// Complete a proposed authority transfer, signed by the new authority
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
+    ) -> Result<()> {
+        let session = &mut ctx.accounts.session;
+        let new_authority = ctx.accounts.new_authority.key();
+
+        require!(
+            session.pending_authority == Some(new_authority),
+            ErrorCode::NotPendingAuthority
+        );
 
-        msg!("Agents selected for session: {}, count: {}", session.session_id, agent_ids.len());
+        session.authority = new_authority;
+        session.pending_authority = None;
 
+        msg!("Authority transferred to {}", new_authority);
         Ok(())
     }
 
+    /// Report each verification check individually, so a failed selection
+    /// shows which check it failed
+    pub fn verify_selection_report(
+        ctx: Context<VerifySelection>,
+    ) -> Result<SelectionReport> {
+        let session = &ctx.accounts.session;
+
+        require!(
+            session.status == SessionStatus::AgentsSelected,
+            ErrorCode::InvalidSessionStatus
+        );
+
+        Ok(session.verification_report())
+    }
+
     /// Verify a council selection
     pub fn verify_selection(
         ctx: Context<VerifySelection>,
@@ -114,19 +553,93 @@ pub mod council_selection {
             ErrorCode::InvalidSessionStatus
         );
 
-        // Verification logic:
-        // 1. Check VRF proof is valid
-        // 2. Check number of agents matches requirement
-        // 3. Check diversity if required
+        let is_valid = session.selection_is_valid();
 
-        let is_valid = session.vrf_fulfilled
-            && session.selected_agents.len() == session.required_agents as usize
-            && session.vrf_proof.len() > 0;
-
-        msg!("Selection verification: {}", is_valid);
+        msg!(
+            "Selection verification: {}, distinct categories: {} (min {}), VRF-backed: {}",
+            is_valid,
+            distinct_category_count(&session.agent_categories),
+            session.min_distinct_categories,
+            !session.deterministic
+        );
 
         Ok(is_valid)
     }
+
+    /// Re-run the deterministic draw from `random_number` and report whether
+    /// it reproduces `selected_agents`
+    ///
+    /// `agent_pool` must hash to the `pool_hash` committed at init. Candidates
+    /// skipped for `service_cooldown_seconds` are skipped again, per
+    /// `cooldown_skipped`.
+    pub fn recompute_selection(
+        ctx: Context<RecomputeSelection>,
+        agent_pool: Vec<String>,
+    ) -> Result<bool> {
+        let session = &ctx.accounts.session;
+
+        require!(
+            session.status == SessionStatus::AgentsSelected
+                || session.status == SessionStatus::Completed,
+            ErrorCode::InvalidSessionStatus
+        );
+
+        require!(
+            pool_hash(&agent_pool) == session.pool_hash,
+            ErrorCode::PoolHashMismatch
+        );
+
+        let picks = session.pick_indices(&session.derived_eligibility())?;
+        let matches = picks.len() == session.selected_agents.len()
+            && picks
+                .iter()
+                .zip(&session.selected_agents)
+                .all(|(&i, agent)| agent_pool[i] == *agent);
+
+        msg!("Selection recomputed for session {}: matches = {}", session.session_id, matches);
+        Ok(matches)
+    }
+
+    /// Read how `derive_selection` seated each agent
+    pub fn get_selection_log(
+        ctx: Context<GetSelectionLog>,
+    ) -> Result<Vec<SelectionStep>> {
+        let session = &ctx.accounts.session;
+
+        require!(
+            session.status == SessionStatus::AgentsSelected
+                || session.status == SessionStatus::Completed,
+            ErrorCode::InvalidSessionStatus
+        );
+
+        Ok(session.selection_log.clone())
+    }
+
+    /// Read the selected council with its randomness provenance
+    pub fn get_selection(
+        ctx: Context<GetSelection>,
+    ) -> Result<SelectionResult> {
+        let session = &ctx.accounts.session;
+
+        require!(
+            session.status == SessionStatus::AgentsSelected
+                || session.status == SessionStatus::Completed,
+            ErrorCode::InvalidSessionStatus
+        );
+
+        Ok(SelectionResult {
+            session_id: session.session_id.clone(),
+            selected_agents: session.selected_agents.clone(),
+            agent_categories: session.agent_categories.clone(),
+            random_number: session.random_number,
+            vrf_proof: session.vrf_proof.clone(),
+            diversity_required: session.min_distinct_categories >= session.required_agents,
+            min_distinct_categories: session.min_distinct_categories,
+            status: session.status.clone(),
+            pool_hash: session.pool_hash,
+            deterministic: session.deterministic,
+        })
+    }
 }
 
 #[derive(Accounts)]
@@ -135,8 +648,8 @@ pub struct InitializeSession<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + CouncilSession::INIT_SPACE,
-        seeds = [b"session", session_id.as_bytes()],
+        space = CouncilSession::ACCOUNT_SPACE,
+        seeds = [CouncilSession::SEED, session_id.as_bytes()],
         bump
     )]
     pub session: Account<'info, CouncilSession>,
@@ -153,6 +666,20 @@ pub struct RequestVRF<'info> {
     pub session: Account<'info, CouncilSession>,
 
     pub authority: Signer<'info>,
+
+    /// CHECK: Parsed and owner-checked by `SwitchboardRandomness::parse`
+    pub randomness_account: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReRequestVRF<'info> {
+    #[account(mut, has_one = authority)]
+    pub session: Account<'info, CouncilSession>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Parsed and owner-checked by `SwitchboardRandomness::parse`
+    pub randomness_account: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
@@ -161,10 +688,18 @@ pub struct FulfillVRF<'info> {
     pub session: Account<'info, CouncilSession>,
 
     pub authority: Signer<'info>,
+
+    /// CHECK: Must match the account committed at `request_vrf`; parsed by `SwitchboardRandomness::parse`
+    pub randomness_account: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyVRFSeed<'info> {
+    pub session: Account<'info, CouncilSession>,
 }
 
 #[derive(Accounts)]
-pub struct SelectAgents<'info> {
+pub struct SelectAgentsDeterministic<'info> {
     #[account(mut, has_one = authority)]
     pub session: Account<'info, CouncilSession>,
 
@@ -172,47 +707,1220 @@ pub struct SelectAgents<'info> {
 }
 
 #[derive(Accounts)]
-pub struct VerifySelection<'info> {
+pub struct DeriveSelection<'info> {
+    #[account(mut)]
     pub session: Account<'info, CouncilSession>,
 }
 
-#[account]
-pub struct CouncilSession {
-    pub session_id: String,           // 32 bytes (max)
-    pub authority: Pubkey,             // 32 bytes
-    pub required_agents: u8,           // 1 byte
-    pub diversity_required: bool,      // 1 byte
-    pub selected_agents: Vec<String>,  // Dynamic (max 10 * 32 = 320 bytes)
-    pub vrf_seed: u64,                 // 8 bytes
-    pub vrf_fulfilled: bool,           // 1 byte
-    pub random_number: u64,            // 8 bytes
-    pub vrf_proof: Vec<u8>,            // Dynamic (max 256 bytes)
-    pub timestamp: i64,                // 8 bytes
-    pub selection_timestamp: i64,      // 8 bytes
-    pub status: SessionStatus,         // 1 byte
-}
+#[derive(Accounts)]
+pub struct InitializeAgentRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgentRegistry::INIT_SPACE,
+        seeds = [AgentRegistry::SEED],
+        bump
+    )]
+    pub registry: Account<'info, AgentRegistry>,
 
-impl CouncilSession {
-    pub const INIT_SPACE: usize = 32 + 32 + 1 + 1 + (4 + 320) + 8 + 1 + 8 + (4 + 256) + 8 + 8 + 1;
-}
+    /// This program's data account, which records its upgrade authority
+    #[account(seeds = [crate::ID.as_ref()], bump, seeds::program = bpf_loader_upgradeable::ID)]
+    pub program_data: Account<'info, ProgramData>,
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum SessionStatus {
-    Initialized,
-    VRFRequested,
-    VRFFulfilled,
-    AgentsSelected,
-    Completed,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Invalid session status for this operation")]
-    InvalidSessionStatus,
-    #[msg("Invalid VRF proof")]
-    InvalidVRFProof,
+#[derive(Accounts)]
+#[instruction(agent_id: String)]
+pub struct RegisterAgent<'info> {
+    #[account(seeds = [AgentRegistry::SEED], bump, has_one = authority)]
+    pub registry: Account<'info, AgentRegistry>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgentProfile::INIT_SPACE,
+        seeds = [AgentProfile::SEED, agent_id.as_bytes()],
+        bump
+    )]
+    pub profile: Account<'info, AgentProfile>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent_id: String)]
+pub struct UpdateAgentProfile<'info> {
+    #[account(seeds = [AgentRegistry::SEED], bump, has_one = authority)]
+    pub registry: Account<'info, AgentRegistry>,
+
+    #[account(mut, seeds = [AgentProfile::SEED, agent_id.as_bytes()], bump)]
+    pub profile: Account<'info, AgentProfile>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent_id: String)]
+pub struct IsEligible<'info> {
+    pub session: Account<'info, CouncilSession>,
+
+    #[account(seeds = [AgentProfile::SEED, agent_id.as_bytes()], bump)]
+    pub profile: Account<'info, AgentProfile>,
+}
+
+#[derive(Accounts)]
+pub struct ReplaceAgent<'info> {
+    #[account(mut, has_one = authority)]
+    pub session: Account<'info, CouncilSession>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSession<'info> {
+    #[account(mut, has_one = authority)]
+    pub session: Account<'info, CouncilSession>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSession<'info> {
+    #[account(mut, has_one = authority)]
+    pub session: Account<'info, CouncilSession>,
+
+    // Created on first use so deployments predating the stats keep finalizing
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + SelectionStats::INIT_SPACE,
+        seeds = [SelectionStats::SEED],
+        bump
+    )]
+    pub stats: Account<'info, SelectionStats>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeSelectionStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SelectionStats::INIT_SPACE,
+        seeds = [SelectionStats::SEED],
+        bump
+    )]
+    pub stats: Account<'info, SelectionStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetSelectionStats<'info> {
+    #[account(seeds = [SelectionStats::SEED], bump)]
+    pub stats: Account<'info, SelectionStats>,
+}
+
+#[derive(Accounts)]
+pub struct VerifySelection<'info> {
+    pub session: Account<'info, CouncilSession>,
+}
+
+#[derive(Accounts)]
+pub struct GetSelection<'info> {
+    pub session: Account<'info, CouncilSession>,
+}
+
+#[derive(Accounts)]
+pub struct GetSelectionLog<'info> {
+    pub session: Account<'info, CouncilSession>,
+}
+
+#[derive(Accounts)]
+pub struct RecomputeSelection<'info> {
+    pub session: Account<'info, CouncilSession>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(mut, has_one = authority)]
+    pub session: Account<'info, CouncilSession>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut)]
+    pub session: Account<'info, CouncilSession>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[account]
+pub struct CouncilSession {
+    pub session_id: String,           // 32 bytes (max)
+    pub authority: Pubkey,             // 32 bytes
+    pub required_agents: u8,           // 1 byte
+    pub min_distinct_categories: u8,   // 1 byte
+    pub selected_agents: Vec<String>,  // Dynamic (max 10 * 32 = 320 bytes)
+    pub vrf_seed: u64,                 // 8 bytes
+    pub vrf_fulfilled: bool,           // 1 byte
+    pub random_number: u64,            // 8 bytes
+    pub vrf_proof: Vec<u8>,            // Dynamic (max 256 bytes)
+    pub timestamp: i64,                // 8 bytes
+    pub selection_timestamp: i64,      // 8 bytes
+    pub status: SessionStatus,         // 1 byte
+    pub agent_categories: Vec<u8>,     // Dynamic (max 10 bytes, parallel to selected_agents)
+    pub randomness_account: Pubkey,    // 32 bytes
+    pub randomness_seed_slot: u64,     // 8 bytes
+    pub agent_pool: Vec<String>,       // Dynamic (max 32 * 32 = 1024 bytes)
+    pub pool_categories: Vec<u8>,      // Dynamic (max 32 bytes, parallel to agent_pool)
+    pub completion_timestamp: i64,     // 8 bytes
+    pub category_quotas: Vec<CategoryQuota>, // Dynamic (max 10 * 2 = 20 bytes)
+    pub pending_authority: Option<Pubkey>, // 33 bytes
+    pub vrf_requested_at: i64,         // 8 bytes
+    pub vrf_timeout_seconds: i64,      // 8 bytes
+    pub service_cooldown_seconds: i64, // 8 bytes
+    pub agent_weights: Vec<u64>,       // Dynamic (max 32 * 8 = 256 bytes, parallel to agent_pool)
+    pub selection_log: Vec<SelectionStep>, // Dynamic (max 10 * SelectionStep::SPACE)
+    pub pool_hash: [u8; 32],           // 32 bytes (commitment to agent_pool, see `pool_hash`)
+    pub replacements: Vec<Replacement>, // Dynamic (MAX_REPLACEMENTS * Replacement::SPACE)
+    pub allow_deterministic: bool,     // 1 byte (fixed at init)
+    pub deterministic: bool,           // 1 byte (selected without VRF)
+    pub cooldown_skipped: u32,         // 4 bytes (bit i: agent_pool[i] was on cooldown at derive_selection)
+}
+
+/// One seat filled by `derive_selection`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SelectionStep {
+    pub slot: u8,                      // 1 byte
+    pub pool_index: u8,                // 1 byte
+    pub agent_id: String,              // 32 bytes (max)
+}
+
+impl SelectionStep {
+    pub const SPACE: usize = 1 + 1 + (4 + MAX_AGENT_ID_LEN);
+}
+
+/// A selected agent swapped out by `replace_agent`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Replacement {
+    pub index: u8,                     // 1 byte (seat in selected_agents)
+    pub removed: String,               // 32 bytes (max)
+    pub replacement: String,           // 32 bytes (max)
+}
+
+impl Replacement {
+    pub const SPACE: usize = 1 + (4 + MAX_AGENT_ID_LEN) + (4 + MAX_AGENT_ID_LEN);
+}
+
+/// An agent's identity and standing, shared by selection and voting
+#[account]
+pub struct AgentProfile {
+    pub agent_id: String,              // 32 bytes (max)
+    pub pubkey: Pubkey,                // 32 bytes (key the agent signs with)
+    pub category: u8,                  // 1 byte
+    pub reputation: u16,               // 2 bytes (NEUTRAL_REPUTATION = 1.0x, at most MAX_REPUTATION)
+    pub last_served: i64,              // 8 bytes
+}
+
+impl AgentProfile {
+    pub const INIT_SPACE: usize = (4 + MAX_AGENT_ID_LEN) + 32 + 1 + 2 + 8;
+
+    /// PDA seed prefix; profiles live at `[SEED, agent_id]`
+    pub const SEED: &'static [u8] = b"agent_profile";
+
+    /// A newly registered profile, at `NEUTRAL_REPUTATION`
+    pub fn new(agent_id: String, pubkey: Pubkey, category: u8) -> Result<Self> {
+        require!(
+            agent_id.len() <= MAX_AGENT_ID_LEN,
+            ErrorCode::AgentIdTooLong
+        );
+
+        Ok(Self {
+            agent_id,
+            pubkey,
+            category,
+            reputation: NEUTRAL_REPUTATION,
+            last_served: 0,
+        })
+    }
+
+    /// Set `reputation`, which may not exceed `MAX_REPUTATION`
+    pub fn set_reputation(&mut self, reputation: u16) -> Result<()> {
+        require!(
+            reputation <= MAX_REPUTATION,
+            ErrorCode::ReputationTooHigh
+        );
+
+        self.reputation = reputation;
+        Ok(())
+    }
+
+    /// Whether `cooldown` seconds have passed since `last_served` at `at`
+    pub fn is_eligible(&self, cooldown: i64, at: i64) -> bool {
+        at.saturating_sub(self.last_served) >= cooldown
+    }
+}
+
+/// Who may register agents and set their reputation; a single program-wide PDA
+#[account]
+pub struct AgentRegistry {
+    pub authority: Pubkey,             // 32 bytes
+}
+
+impl AgentRegistry {
+    pub const INIT_SPACE: usize = 32;
+
+    /// PDA seed; the registry lives at `[SEED]`
+    pub const SEED: &'static [u8] = b"agent_registry";
+}
+
+/// Running totals across every finalized session; a single program-wide PDA
+#[account]
+pub struct SelectionStats {
+    pub sessions_finalized: u64,       // 8 bytes
+    pub diverse_sessions: u64,         // 8 bytes (every seat from a different category)
+    pub total_pool_size: u64,          // 8 bytes (sum of candidate pool lengths)
+}
+
+impl SelectionStats {
+    pub const INIT_SPACE: usize = 8 + 8 + 8;
+
+    /// PDA seed; the stats account lives at `[SEED]`
+    pub const SEED: &'static [u8] = b"selection_stats";
+
+    /// Mean candidate pool size in hundredths (0 before any session finalizes)
+    pub fn average_pool_size(&self) -> u64 {
+        self.total_pool_size.saturating_mul(100)
+            .checked_div(self.sessions_finalized)
+            .unwrap_or(0)
+    }
+}
+
+/// Selection subsystem health, as reported by `get_selection_stats`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SelectionStatsSummary {
+    pub sessions_finalized: u64,
+    /// Sessions whose council drew every seat from a different category
+    pub diverse_sessions: u64,
+    /// Mean candidate pool size, in hundredths (e.g. 1250 for 12.5)
+    pub average_pool_size: u64,
+}
+
+/// A session's selected council and the randomness it was drawn from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SelectionResult {
+    pub session_id: String,
+    pub selected_agents: Vec<String>,
+    /// Category of each selected agent, parallel to `selected_agents`
+    pub agent_categories: Vec<u8>,
+    pub random_number: u64,
+    pub vrf_proof: Vec<u8>,
+    /// Whether every selected agent had to come from a different category
+    pub diversity_required: bool,
+    pub min_distinct_categories: u8,
+    pub status: SessionStatus,
+    /// Commitment to the candidate pool, for `recompute_selection`
+    pub pool_hash: [u8; 32],
+    /// Seated without VRF by `select_agents_deterministic`
+    pub deterministic: bool,
+}
+
+/// Per-check result of verifying a selection
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SelectionReport {
+    pub vrf_fulfilled: bool,
+    /// Seated by `select_agents_deterministic`, so not VRF-backed; the VRF
+    /// checks are waived but the selection should not be trusted as random
+    pub deterministic: bool,
+    /// Selected agents and their categories both number `required_agents`
+    pub count_matches: bool,
+    pub proof_present: bool,
+    /// At least `min_distinct_categories` categories are represented
+    pub diversity_ok: bool,
+    /// Every category quota is filled exactly (true when none are set)
+    pub quotas_ok: bool,
+    /// `selected_agents` is the council `random_number` draws from the pool,
+    /// after any recorded replacements
+    pub matches_draw: bool,
+    pub distinct_categories: u8,
+}
+
+impl SelectionReport {
+    /// Whether every check passed
+    pub fn is_valid(&self) -> bool {
+        (self.deterministic || (self.vrf_fulfilled && self.proof_present && self.matches_draw))
+            && self.count_matches
+            && self.diversity_ok
+            && self.quotas_ok
+    }
+}
+
+/// Parameters supplied when a session is created
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SessionConfig {
+    pub required_agents: u8,
+    /// Legacy switch; `true` requires every selected agent to have a distinct
+    /// category, i.e. `min_distinct_categories == required_agents`
+    pub diversity_required: bool,
+    /// Minimum number of different categories among the selected agents
+    pub min_distinct_categories: u8,
+    /// Candidates the council is drawn from
+    pub agent_pool: Vec<String>,
+    /// Category of each pool candidate, parallel to `agent_pool`
+    pub pool_categories: Vec<u8>,
+    /// Seats reserved per category (empty disables stratified selection)
+    pub category_quotas: Vec<CategoryQuota>,
+    /// Seconds after a VRF request before `re_request_vrf` may replace it
+    pub vrf_timeout_seconds: i64,
+    /// Seconds an agent must sit out after serving before it can be selected again
+    pub service_cooldown_seconds: i64,
+    /// Selection weight of each candidate, parallel to `agent_pool` (empty
+    /// selects uniformly; zero-weight candidates are never selected)
+    pub agent_weights: Vec<u64>,
+    /// Accept `select_agents_deterministic`, which seats agents without VRF.
+    /// For test and demo environments only.
+    pub allow_deterministic: bool,
+}
+
+impl SessionConfig {
+    /// Effective diversity threshold, honouring the legacy boolean
+    pub fn min_distinct_categories(&self) -> u8 {
+        if self.diversity_required {
+            self.required_agents.max(self.min_distinct_categories)
+        } else {
+            self.min_distinct_categories
+        }
+    }
+}
+
+/// Number of seats reserved for one category
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CategoryQuota {
+    pub category: u8,
+    pub count: u8,
+}
+
+impl CouncilSession {
+    pub const INIT_SPACE: usize = 32 + 32 + 1 + 1 + (4 + 320) + 8 + 1 + 8 + (4 + 256) + 8 + 8 + 1 + (4 + 10) + 32 + 8
+        + (4 + MAX_AGENT_POOL * (4 + 32)) + (4 + MAX_AGENT_POOL) + 8 + (4 + MAX_CATEGORY_QUOTAS * 2) + 33 + 8 + 8 + 8
+        + (4 + MAX_AGENT_POOL * 8) + (4 + 10 * SelectionStep::SPACE) + 32
+        + (4 + MAX_REPLACEMENTS * Replacement::SPACE) + 1 + 1 + 4;
+
+    /// Bytes to allocate for a session account, discriminator included
+    pub const ACCOUNT_SPACE: usize = 8 + Self::INIT_SPACE;
+
+    /// PDA seed prefix; sessions live at `[SEED, session_id]`
+    pub const SEED: &'static [u8] = b"session";
+
+    /// Address and bump of the session PDA for `session_id`, for clients
+    /// preparing many `initialize_session` calls off-chain
+    pub fn address(session_id: &str) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED, session_id.as_bytes()], &crate::ID)
+    }
+
+    /// Verification logic:
+    /// 1. Check VRF proof is valid (waived for deterministic selections)
+    /// 2. Check number of agents matches requirement
+    /// 3. Check at least `min_distinct_categories` categories are represented
+    /// 4. Check category quotas if set
+    /// 5. Check the council is the one drawn from the pool (waived for
+    ///    deterministic selections)
+    pub fn selection_is_valid(&self) -> bool {
+        self.verification_report().is_valid()
+    }
+
+    /// Outcome of each verification check
+    pub fn verification_report(&self) -> SelectionReport {
+        let distinct_categories = distinct_category_count(&self.agent_categories);
+
+        SelectionReport {
+            vrf_fulfilled: self.vrf_fulfilled,
+            deterministic: self.deterministic,
+            count_matches: self.selected_agents.len() == self.required_agents as usize
+                && self.agent_categories.len() == self.selected_agents.len(),
+            proof_present: !self.vrf_proof.is_empty(),
+            diversity_ok: distinct_categories >= self.min_distinct_categories as usize,
+            quotas_ok: self.quotas_met(&self.agent_categories),
+            matches_draw: self.drawn_council().is_some_and(|council| council == self.selected_agents),
+            distinct_categories: distinct_categories as u8,
+        }
+    }
+
+    /// Which pool candidates `derive_selection` treated as eligible, per
+    /// `cooldown_skipped`
+    pub fn derived_eligibility(&self) -> Vec<bool> {
+        (0..self.agent_pool.len())
+            .map(|i| self.cooldown_skipped & (1 << i) == 0)
+            .collect()
+    }
+
+    /// The council `random_number` draws from the pool, with `replacements`
+    /// applied in order; None when the draw fails or a replacement does not
+    /// fit it
+    pub fn drawn_council(&self) -> Option<Vec<String>> {
+        let picks = self.pick_indices(&self.derived_eligibility()).ok()?;
+        let mut council: Vec<String> = picks.iter().map(|&i| self.agent_pool[i].clone()).collect();
+        for replacement in &self.replacements {
+            let seat = council.get_mut(replacement.index as usize)?;
+            if *seat != replacement.removed {
+                return None;
+            }
+            *seat = replacement.replacement.clone();
+        }
+        Some(council)
+    }
+
+    /// Pool indices of the seated candidates, in the order they are picked,
+    /// skipping candidates that are not `eligible`
+    pub fn pick_indices(&self, eligible: &[bool]) -> Result<Vec<usize>> {
+        let mut picks: Vec<usize> = Vec::new();
+        if self.category_quotas.is_empty() {
+            let pool_len = self.agent_pool.len();
+            let required = self.required_agents as usize;
+            require!(
+                eligible.iter().filter(|&&e| e).count() >= required,
+                ErrorCode::AgentOnCooldown
+            );
+            let all: Vec<usize> = (0..pool_len).collect();
+            let order = self.draw_order(self.random_number, &all, pool_len);
+            // Agents on cooldown start out taken so neither pass seats them
+            let mut taken: Vec<bool> = eligible.iter().map(|&e| !e).collect();
+
+            // Seat the first candidate of each new category until the
+            // diversity threshold is covered, then fill in shuffle order
+            let mut seen = [false; 256];
+            let mut distinct = 0;
+            for &index in &order {
+                if distinct == self.min_distinct_categories as usize {
+                    break;
+                }
+                let category = self.pool_categories[index];
+                if !taken[index] && !seen[category as usize] {
+                    seen[category as usize] = true;
+                    distinct += 1;
+                    taken[index] = true;
+                    picks.push(index);
+                }
+            }
+            for &index in &order {
+                if picks.len() == required {
+                    break;
+                }
+                if !taken[index] {
+                    taken[index] = true;
+                    picks.push(index);
+                }
+            }
+        } else {
+            // Stratified: each bucket fills its quota from its own seeded shuffle
+            for quota in &self.category_quotas {
+                let bucket: Vec<usize> = (0..self.agent_pool.len())
+                    .filter(|&i| eligible[i] && self.pool_categories[i] == quota.category)
+                    .collect();
+                require!(
+                    bucket.len() >= quota.count as usize,
+                    ErrorCode::AgentOnCooldown
+                );
+                let seed = bucket_seed(self.random_number, quota.category);
+                picks.extend(self.draw_order(seed, &bucket, quota.count as usize));
+            }
+        }
+
+        require!(
+            picks.len() == self.required_agents as usize,
+            ErrorCode::DiversityViolation
+        );
+
+        Ok(picks)
+    }
+
+    /// Draw up to `pick` of the `candidates` (pool indices) in seeded order:
+    /// uniformly, or in proportion to `agent_weights` when weights are set
+    pub fn draw_order(&self, seed: u64, candidates: &[usize], pick: usize) -> Vec<usize> {
+        let draws = if self.agent_weights.is_empty() {
+            weighted_shuffle(seed, candidates.len(), pick)
+        } else {
+            let weights: Vec<u64> = candidates.iter().map(|&i| self.agent_weights[i]).collect();
+            weighted_sample(seed, &weights, pick)
+        };
+        draws.into_iter().map(|d| candidates[d]).collect()
+    }
+
+    /// Whether `categories` fills every quota exactly; true when no quotas are set
+    pub fn quotas_met(&self, categories: &[u8]) -> bool {
+        self.category_quotas.iter().all(|quota| {
+            categories.iter().filter(|&&c| c == quota.category).count() == quota.count as usize
+        })
+    }
+}
+
+/// VRF timeout used by `initialize_session_with_pool`
+pub const DEFAULT_VRF_TIMEOUT_SECONDS: i64 = 300;
+
+/// Maximum length of a session id, in bytes; also the PDA seed limit
+pub const MAX_SESSION_ID_LEN: usize = 32;
+
+/// Maximum length of an agent id, in bytes. Ten selected agents at this
+/// length fill the 320 bytes reserved for `selected_agents`.
+pub const MAX_AGENT_ID_LEN: usize = 32;
+
+/// Maximum number of candidates in a session's agent pool
+pub const MAX_AGENT_POOL: usize = 32;
+
+/// Maximum number of category quotas; one per seat at most
+pub const MAX_CATEGORY_QUOTAS: usize = 10;
+
+/// Maximum number of `replace_agent` swaps a session can record
+pub const MAX_REPLACEMENTS: usize = 10;
+
+/// Reputation a newly registered agent starts at; voting scales a vote by
+/// `reputation / NEUTRAL_REPUTATION`
+pub const NEUTRAL_REPUTATION: u16 = 1000;
+
+/// Highest reputation an agent can hold, doubling its vote weight
+pub const MAX_REPUTATION: u16 = 2000;
+
+/// Hash of an ordered agent pool; each id is length-prefixed so that
+/// different pools cannot concatenate to the same bytes
+pub fn pool_hash(agent_pool: &[String]) -> [u8; 32] {
+    let mut bytes = Vec::new();
+    for agent_id in agent_pool {
+        bytes.extend_from_slice(&(agent_id.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(agent_id.as_bytes());
+    }
+    hashv(&[&bytes]).to_bytes()
+}
+
+/// Validate `config` and set up a new session; shared by both initializers
+fn init_session(
+    session: &mut CouncilSession,
+    authority: Pubkey,
+    session_id: String,
+    config: SessionConfig,
+) -> Result<()> {
+    require!(
+        !session_id.is_empty(),
+        ErrorCode::EmptySessionId
+    );
+
+    require!(
+        session_id.len() <= MAX_SESSION_ID_LEN,
+        ErrorCode::SessionIdTooLong
+    );
+
+    let required_agents = config.required_agents;
+    require!(
+        config.agent_pool.len() >= required_agents as usize
+            && config.agent_pool.len() <= MAX_AGENT_POOL,
+        ErrorCode::InvalidAgentPool
+    );
+    validate_agent_ids(&config.agent_pool)?;
+
+    require!(
+        config.pool_categories.len() == config.agent_pool.len(),
+        ErrorCode::CategoryCountMismatch
+    );
+
+    require!(
+        config.vrf_timeout_seconds > 0,
+        ErrorCode::InvalidVrfTimeout
+    );
+
+    require!(
+        config.service_cooldown_seconds >= 0,
+        ErrorCode::InvalidServiceCooldown
+    );
+
+    require!(
+        config.agent_weights.is_empty()
+            || (config.agent_weights.len() == config.agent_pool.len()
+                && config.agent_weights.iter().filter(|&&w| w > 0).count()
+                    >= required_agents as usize),
+        ErrorCode::InvalidAgentWeights
+    );
+
+    let min_distinct_categories = config.min_distinct_categories();
+    require!(
+        min_distinct_categories <= required_agents,
+        ErrorCode::InvalidDiversityThreshold
+    );
+    require!(
+        distinct_category_count(&config.pool_categories) >= min_distinct_categories as usize,
+        ErrorCode::DiversityViolation
+    );
+
+    validate_quotas(
+        &config.category_quotas,
+        required_agents,
+        min_distinct_categories,
+        &config.pool_categories,
+    )?;
+
+    session.session_id = session_id;
+    session.authority = authority;
+    session.required_agents = required_agents;
+    session.min_distinct_categories = min_distinct_categories;
+    session.selected_agents = Vec::new();
+    session.agent_categories = Vec::new();
+    session.pool_hash = pool_hash(&config.agent_pool);
+    session.replacements = Vec::new();
+    session.allow_deterministic = config.allow_deterministic;
+    session.deterministic = false;
+    session.cooldown_skipped = 0;
+    session.agent_pool = config.agent_pool;
+    session.pool_categories = config.pool_categories;
+    session.category_quotas = config.category_quotas;
+    session.vrf_timeout_seconds = config.vrf_timeout_seconds;
+    session.service_cooldown_seconds = config.service_cooldown_seconds;
+    session.agent_weights = config.agent_weights;
+    session.vrf_seed = 0;
+    session.vrf_fulfilled = false;
+    session.timestamp = Clock::get()?.unix_timestamp;
+    session.status = SessionStatus::Initialized;
+
+    msg!("Council session initialized: {}", session.session_id);
+    Ok(())
+}
+
+/// Validate and store a hand-picked council for
+/// `select_agents_deterministic`. Service records, when supplied in
+/// `records`, are parallel to `agent_ids`.
+fn seat_agents(
+    session: &mut CouncilSession,
+    agent_ids: Vec<String>,
+    agent_categories: Vec<u8>,
+    records: &[AccountInfo],
+) -> Result<()> {
+    require!(
+        agent_ids.len() == session.required_agents as usize,
+        ErrorCode::InvalidAgentCount
+    );
+
+    require!(
+        agent_categories.len() == agent_ids.len(),
+        ErrorCode::CategoryCountMismatch
+    );
+
+    validate_agent_ids(&agent_ids)?;
+
+    require!(
+        distinct_category_count(&agent_categories) >= session.min_distinct_categories as usize,
+        ErrorCode::DiversityViolation
+    );
+
+    require!(
+        session.quotas_met(&agent_categories),
+        ErrorCode::QuotaMismatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let eligible = service_eligibility(records, &agent_ids, session.service_cooldown_seconds, now)?;
+    require!(
+        eligible.iter().all(|&e| e),
+        ErrorCode::AgentOnCooldown
+    );
+    record_service(records, &agent_ids, &agent_ids, now)?;
+
+    session.selected_agents = agent_ids.clone();
+    session.agent_categories = agent_categories;
+    // Manual selections are not derived from the pool, so there is nothing to log
+    session.selection_log = Vec::new();
+    session.status = SessionStatus::AgentsSelected;
+    session.selection_timestamp = now;
+
+    msg!("Agents selected for session: {}, count: {}", session.session_id, agent_ids.len());
+
+    Ok(())
+}
+
+/// Commit a session to a Switchboard randomness account and seed
+fn record_vrf_request(
+    session: &mut CouncilSession,
+    randomness_account: &AccountInfo,
+    vrf_seed: u64,
+) -> Result<()> {
+    // The randomness account must have been committed in the previous slot,
+    // so its value cannot already be known when the request is recorded
+    let randomness = SwitchboardRandomness::parse(randomness_account)?;
+    let clock = Clock::get()?;
+    require!(
+        randomness.seed_slot == clock.slot.saturating_sub(1),
+        ErrorCode::StaleRandomness
+    );
+
+    session.vrf_seed = vrf_seed;
+    session.randomness_account = randomness_account.key();
+    session.randomness_seed_slot = randomness.seed_slot;
+    session.vrf_requested_at = clock.unix_timestamp;
+    session.status = SessionStatus::VRFRequested;
+
+    emit!(VrfRequested {
+        session_id: session.session_id.clone(),
+        randomness_account: session.randomness_account,
+        vrf_seed,
+    });
+
+    msg!("VRF requested for session: {}, seed: {}", session.session_id, vrf_seed);
+
+    Ok(())
+}
+
+/// Read the profiles of `agent_ids` from `records`, which must hold exactly
+/// those profiles in order
+fn load_profiles(records: &[AccountInfo], agent_ids: &[String]) -> Result<Vec<AgentProfile>> {
+    require!(
+        records.len() == agent_ids.len(),
+        ErrorCode::InvalidAgentProfile
+    );
+    records
+        .iter()
+        .zip(agent_ids)
+        .map(|(record, agent_id)| {
+            let (expected, _) =
+                Pubkey::find_program_address(&[AgentProfile::SEED, agent_id.as_bytes()], &crate::ID);
+            require!(
+                record.key() == expected && record.owner == &crate::ID,
+                ErrorCode::InvalidAgentProfile
+            );
+            AgentProfile::try_deserialize(&mut &record.try_borrow_data()?[..])
+        })
+        .collect()
+}
+
+/// Which of `agent_ids` are off cooldown at `now`, read from their profiles.
+/// Without a cooldown the profiles are optional and every agent is eligible.
+fn service_eligibility(
+    records: &[AccountInfo],
+    agent_ids: &[String],
+    cooldown: i64,
+    now: i64,
+) -> Result<Vec<bool>> {
+    if cooldown == 0 && records.is_empty() {
+        return Ok(vec![true; agent_ids.len()]);
+    }
+    Ok(load_profiles(records, agent_ids)?
+        .iter()
+        .map(|profile| profile.is_eligible(cooldown, now))
+        .collect())
+}
+
+/// Stamp `last_served` on the profiles of the `selected` agents; `records`
+/// are parallel to `agent_ids` and may be omitted when no cooldown is in use
+fn record_service(
+    records: &[AccountInfo],
+    agent_ids: &[String],
+    selected: &[String],
+    now: i64,
+) -> Result<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    for (record, agent_id) in records.iter().zip(agent_ids) {
+        if !selected.contains(agent_id) {
+            continue;
+        }
+        let mut data = record.try_borrow_mut_data()?;
+        let mut profile = AgentProfile::try_deserialize(&mut &data[..])?;
+        profile.last_served = now;
+        profile.try_serialize(&mut &mut data[..])?;
+    }
+    Ok(())
+}
+
+/// Check quotas name distinct categories, sum to `required_agents` and can be
+/// filled from the pool. An empty list disables stratified selection.
+fn validate_quotas(
+    quotas: &[CategoryQuota],
+    required_agents: u8,
+    min_distinct_categories: u8,
+    pool_categories: &[u8],
+) -> Result<()> {
+    if quotas.is_empty() {
+        return Ok(());
+    }
+
+    let categories: Vec<u8> = quotas.iter().map(|q| q.category).collect();
+    let total: u32 = quotas.iter().map(|q| q.count as u32).sum();
+    require!(
+        quotas.len() <= MAX_CATEGORY_QUOTAS
+            && categories_are_distinct(&categories)
+            && total == required_agents as u32,
+        ErrorCode::QuotaMismatch
+    );
+
+    for quota in quotas {
+        let available = pool_categories.iter().filter(|&&c| c == quota.category).count();
+        require!(
+            available >= quota.count as usize,
+            ErrorCode::QuotaMismatch
+        );
+    }
+
+    // Quotas fix the selected categories, so they alone decide diversity
+    let represented = quotas.iter().filter(|q| q.count > 0).count();
+    require!(
+        represented >= min_distinct_categories as usize,
+        ErrorCode::DiversityViolation
+    );
+    Ok(())
+}
+
+/// Check agent ids are distinct and fit the reserved space
+fn validate_agent_ids(agent_ids: &[String]) -> Result<()> {
+    for (i, agent_id) in agent_ids.iter().enumerate() {
+        require!(
+            agent_id.len() <= MAX_AGENT_ID_LEN,
+            ErrorCode::AgentIdTooLong
+        );
+        require!(
+            !agent_ids[..i].contains(agent_id),
+            ErrorCode::DuplicateAgent
+        );
+    }
+    Ok(())
+}
+
+/// Number of different category ids in `categories`
+fn distinct_category_count(categories: &[u8]) -> usize {
+    let mut seen = [false; 256];
+    let mut count = 0;
+    for &category in categories {
+        if !seen[category as usize] {
+            seen[category as usize] = true;
+            count += 1;
+        }
+    }
+    count
+}
+
+/// True when no category id appears more than once
+fn categories_are_distinct(categories: &[u8]) -> bool {
+    let mut seen = [false; 256];
+    for &category in categories {
+        if seen[category as usize] {
+            return false;
+        }
+        seen[category as usize] = true;
+    }
+    true
+}
+
+/// Switchboard On-Demand program on mainnet
+pub mod switchboard_mainnet {
+    use super::*;
+    declare_id!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+}
+
+/// Switchboard On-Demand program on devnet
+pub mod switchboard_devnet {
+    use super::*;
+    declare_id!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2");
+}
+
+/// Anchor discriminator of Switchboard's `RandomnessAccountData`
+const RANDOMNESS_DISCRIMINATOR: [u8; 8] = [10, 66, 229, 135, 220, 239, 217, 114];
+
+/// Discriminator plus the 400-byte `RandomnessAccountData` body
+const RANDOMNESS_ACCOUNT_LEN: usize = 8 + 400;
+
+/// Fields read from a Switchboard On-Demand randomness account
+///
+/// Body layout (after the discriminator): authority (32), queue (32),
+/// seed_slothash (32), seed_slot (u64), oracle (32), reveal_slot (u64),
+/// value ([u8; 32]), followed by reserved padding.
+pub struct SwitchboardRandomness {
+    pub seed_slot: u64,
+    pub reveal_slot: u64,
+    pub value: [u8; 32],
+}
+
+impl SwitchboardRandomness {
+    pub fn parse(account: &AccountInfo) -> Result<Self> {
+        require!(
+            switchboard_mainnet::check_id(account.owner)
+                || switchboard_devnet::check_id(account.owner),
+            ErrorCode::InvalidRandomnessAccount
+        );
+
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= RANDOMNESS_ACCOUNT_LEN && data[..8] == RANDOMNESS_DISCRIMINATOR,
+            ErrorCode::InvalidRandomnessAccount
+        );
+
+        let body = &data[8..];
+        let read_u64 = |offset: usize| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&body[offset..offset + 8]);
+            u64::from_le_bytes(bytes)
+        };
+        let mut value = [0u8; 32];
+        value.copy_from_slice(&body[144..176]);
+
+        Ok(Self {
+            seed_slot: read_u64(96),
+            reveal_slot: read_u64(136),
+            value,
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum SessionStatus {
+    Initialized,
+    VRFRequested,
+    VRFFulfilled,
+    AgentsSelected,
+    Completed,
+    Cancelled,
+}
+
+#[event]
+pub struct VrfRequested {
+    pub session_id: String,
+    pub randomness_account: Pubkey,
+    pub vrf_seed: u64,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Invalid session status for this operation")]
+    InvalidSessionStatus,
+    #[msg("Invalid VRF proof")]
+    InvalidVRFProof,
     #[msg("Invalid number of agents")]
     InvalidAgentCount,
     #[msg("Session not found")]
     SessionNotFound,
+    #[msg("Agent categories must be provided for every selected agent")]
+    CategoryCountMismatch,
+    #[msg("Selected agents must each come from a different category")]
+    DiversityViolation,
+    #[msg("Randomness is stale or does not match the committed request")]
+    StaleRandomness,
+    #[msg("Account is not a Switchboard On-Demand randomness account")]
+    InvalidRandomnessAccount,
+    #[msg("Agent pool must hold between required_agents and 32 agents")]
+    InvalidAgentPool,
+    #[msg("Agent appears more than once")]
+    DuplicateAgent,
+    #[msg("Agent id exceeds the maximum length")]
+    AgentIdTooLong,
+    #[msg("Selection failed verification")]
+    VerificationFailed,
+    #[msg("Category quotas must be distinct, sum to required_agents and be fillable from the pool")]
+    QuotaMismatch,
+    #[msg("Minimum distinct categories cannot exceed required_agents")]
+    InvalidDiversityThreshold,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("VRF timeout must be positive")]
+    InvalidVrfTimeout,
+    #[msg("VRF request has not timed out yet")]
+    VRFRequestNotExpired,
+    #[msg("Service cooldown cannot be negative")]
+    InvalidServiceCooldown,
+    #[msg("Not enough agents are off their service cooldown")]
+    AgentOnCooldown,
+    #[msg("Agent weights must match the pool and give at least required_agents a nonzero weight")]
+    InvalidAgentWeights,
+    #[msg("Session id cannot be empty")]
+    EmptySessionId,
+    #[msg("Session id exceeds the maximum length")]
+    SessionIdTooLong,
+    #[msg("Agent pool is smaller than the number of agents required")]
+    PoolTooSmall,
+    #[msg("Agent pool does not match the session's pool")]
+    PoolHashMismatch,
+    #[msg("Replacement must name a selected seat and a candidate from the agent pool")]
+    InvalidReplacement,
+    #[msg("Session cannot record more replacements")]
+    ReplacementsFull,
+    #[msg("Session was committed to a different VRF seed")]
+    VRFSeedMismatch,
+    #[msg("Session does not allow deterministic selection")]
+    DeterministicSelectionDisabled,
+    #[msg("Agent is not in the session's agent pool")]
+    AgentNotInPool,
+    #[msg("Agent profiles must be passed in the same order as the agent ids")]
+    InvalidAgentProfile,
+    #[msg("Reputation exceeds the maximum")]
+    ReputationTooHigh,
+    #[msg("Signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn distinct_agents_are_accepted() {
+        assert_eq!(validate_agent_ids(&ids(&["alpha", "beta", "gamma"])), Ok(()));
+    }
+
+    #[test]
+    fn duplicate_agents_are_rejected() {
+        assert_eq!(
+            validate_agent_ids(&ids(&["alpha", "beta", "alpha"])),
+            Err(ErrorCode::DuplicateAgent.into())
+        );
+    }
+
+    #[test]
+    fn oversized_agent_ids_are_rejected() {
+        let long_id = "a".repeat(MAX_AGENT_ID_LEN + 1);
+        assert_eq!(
+            validate_agent_ids(&[long_id]),
+            Err(ErrorCode::AgentIdTooLong.into())
+        );
+    }
+
+    #[test]
+    fn pool_hash_separates_agent_ids() {
+        assert_ne!(pool_hash(&ids(&["ab", "c"])), pool_hash(&ids(&["a", "bc"])));
+        assert_ne!(pool_hash(&ids(&["a", "b"])), pool_hash(&ids(&["b", "a"])));
+        assert_eq!(pool_hash(&ids(&["a", "b"])), pool_hash(&ids(&["a", "b"])));
+    }
+
+    fn profile_address(agent_id: &str) -> Pubkey {
+        Pubkey::find_program_address(&[AgentProfile::SEED, agent_id.as_bytes()], &crate::ID).0
+    }
+
+    fn profile_data(agent_id: &str) -> Vec<u8> {
+        let mut data = Vec::new();
+        AgentProfile::new(agent_id.to_string(), Pubkey::default(), 1)
+            .unwrap()
+            .try_serialize(&mut data)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn registered_agents_start_neutral() {
+        let profile = AgentProfile::new("alpha".to_string(), Pubkey::default(), 2).unwrap();
+        assert_eq!((profile.reputation, profile.last_served), (NEUTRAL_REPUTATION, 0));
+        assert_eq!(
+            AgentProfile::new("a".repeat(MAX_AGENT_ID_LEN + 1), Pubkey::default(), 2).err(),
+            Some(ErrorCode::AgentIdTooLong.into())
+        );
+    }
+
+    #[test]
+    fn reputation_is_capped() {
+        let mut profile = AgentProfile::new("alpha".to_string(), Pubkey::default(), 2).unwrap();
+        assert_eq!(profile.set_reputation(MAX_REPUTATION), Ok(()));
+        assert_eq!(
+            profile.set_reputation(MAX_REPUTATION + 1),
+            Err(ErrorCode::ReputationTooHigh.into())
+        );
+        assert_eq!(profile.reputation, MAX_REPUTATION);
+    }
+
+    #[test]
+    fn cooldown_elapses_after_service() {
+        let mut profile = AgentProfile::new("alpha".to_string(), Pubkey::default(), 2).unwrap();
+        profile.last_served = 100;
+        assert!(!profile.is_eligible(50, 149));
+        assert!(profile.is_eligible(50, 150));
+        assert!(profile.is_eligible(0, 100));
+    }
+
+    #[test]
+    fn profiles_load_only_in_order_from_this_program() {
+        let agents = ids(&["alpha", "beta"]);
+        let keys = [profile_address("alpha"), profile_address("beta")];
+        let (owner, stranger) = (crate::ID, Pubkey::default());
+        let (mut alpha_lamports, mut beta_lamports, mut forged_lamports) = (0u64, 0u64, 0u64);
+        let (mut alpha_data, mut beta_data, mut forged_data) =
+            (profile_data("alpha"), profile_data("beta"), profile_data("alpha"));
+        let records = [
+            AccountInfo::new(&keys[0], false, true, &mut alpha_lamports, &mut alpha_data, &owner, false, 0),
+            AccountInfo::new(&keys[1], false, true, &mut beta_lamports, &mut beta_data, &owner, false, 0),
+        ];
+
+        let loaded = load_profiles(&records, &agents).unwrap();
+        assert_eq!(loaded[0].agent_id, "alpha");
+        assert_eq!(loaded[1].agent_id, "beta");
+        assert_eq!(
+            load_profiles(&records, &ids(&["beta", "alpha"])).err(),
+            Some(ErrorCode::InvalidAgentProfile.into())
+        );
+        assert_eq!(
+            load_profiles(&records[..1], &agents).err(),
+            Some(ErrorCode::InvalidAgentProfile.into())
+        );
+
+        let forged = [AccountInfo::new(&keys[0], false, true, &mut forged_lamports, &mut forged_data, &stranger, false, 0)];
+        assert_eq!(
+            load_profiles(&forged, &ids(&["alpha"])).err(),
+            Some(ErrorCode::InvalidAgentProfile.into())
+        );
+    }
+
+    fn pool_session(categories: &[u8], required_agents: u8) -> CouncilSession {
+        CouncilSession {
+            session_id: "session".to_string(),
+            authority: Pubkey::default(),
+            required_agents,
+            min_distinct_categories: 1,
+            selected_agents: Vec::new(),
+            vrf_seed: 0,
+            vrf_fulfilled: true,
+            random_number: 42,
+            vrf_proof: Vec::new(),
+            timestamp: 0,
+            selection_timestamp: 0,
+            status: SessionStatus::VRFFulfilled,
+            agent_categories: Vec::new(),
+            randomness_account: Pubkey::default(),
+            randomness_seed_slot: 0,
+            agent_pool: (0..categories.len()).map(|i| format!("agent-{}", i)).collect(),
+            pool_categories: categories.to_vec(),
+            completion_timestamp: 0,
+            category_quotas: Vec::new(),
+            pending_authority: None,
+            vrf_requested_at: 0,
+            vrf_timeout_seconds: DEFAULT_VRF_TIMEOUT_SECONDS,
+            service_cooldown_seconds: 0,
+            agent_weights: Vec::new(),
+            selection_log: Vec::new(),
+            pool_hash: [0; 32],
+            replacements: Vec::new(),
+            allow_deterministic: false,
+            deterministic: false,
+            cooldown_skipped: 0,
+        }
+    }
+
+    #[test]
+    fn cooldowns_that_empty_the_pool_are_reported() {
+        let session = pool_session(&[0, 1, 2, 3], 3);
+        assert_eq!(session.pick_indices(&[true; 4]).map(|picks| picks.len()), Ok(3));
+        assert_eq!(
+            session.pick_indices(&[true, false, true, false]),
+            Err(ErrorCode::AgentOnCooldown.into())
+        );
+    }
 }
@@ -0,0 +1,120 @@
+//! Deterministic mapping from VRF output to council seats
+
+/// SplitMix64 step used to expand the VRF output into shuffle draws
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Seeded partial Fisher-Yates shuffle: the first `pick` indices of a random
+/// permutation of `0..pool_len`. `pick` is clamped to `pool_len`, and the same
+/// seed always yields the same indices in the same order.
+pub fn weighted_shuffle(seed: u64, pool_len: usize, pick: usize) -> Vec<usize> {
+    let pick = pick.min(pool_len);
+    let mut indices: Vec<usize> = (0..pool_len).collect();
+    let mut state = seed;
+    for i in 0..pick {
+        let j = i + (splitmix64(&mut state) % (pool_len - i) as u64) as usize;
+        indices.swap(i, j);
+    }
+    indices.truncate(pick);
+    indices
+}
+
+/// Seeded weighted sampling without replacement: each draw picks a remaining
+/// index with probability proportional to its weight. Returns up to `pick`
+/// indices in draw order; zero-weight indices are never drawn.
+pub fn weighted_sample(seed: u64, weights: &[u64], pick: usize) -> Vec<usize> {
+    let mut remaining: Vec<usize> = (0..weights.len()).filter(|&i| weights[i] > 0).collect();
+    let mut drawn = Vec::with_capacity(pick.min(remaining.len()));
+    let mut state = seed;
+    while drawn.len() < pick && !remaining.is_empty() {
+        // At most 32 u64 weights, so the total fits comfortably in u128
+        let total: u128 = remaining.iter().map(|&i| weights[i] as u128).sum();
+        let draw = ((splitmix64(&mut state) as u128) << 64 | splitmix64(&mut state) as u128) % total;
+        let mut cumulative = 0u128;
+        let position = remaining
+            .iter()
+            .position(|&i| {
+                cumulative += weights[i] as u128;
+                draw < cumulative
+            })
+            .unwrap_or(remaining.len() - 1);
+        drawn.push(remaining.remove(position));
+    }
+    drawn
+}
+
+/// Seed for one category bucket, so each bucket is drawn independently of
+/// how many candidates the other buckets hold
+pub fn bucket_seed(seed: u64, category: u8) -> u64 {
+    let mut state = seed ^ ((category as u64) << 56);
+    splitmix64(&mut state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_seeds_produce_known_indices() {
+        assert_eq!(weighted_shuffle(0, 8, 8), vec![7, 2, 3, 0, 1, 5, 4, 6]);
+        assert_eq!(weighted_shuffle(42, 10, 4), vec![3, 2, 4, 5]);
+    }
+
+    #[test]
+    fn picks_are_distinct_and_in_range() {
+        for seed in 0..64 {
+            let mut picked = weighted_shuffle(seed, 32, 12);
+            assert_eq!(picked.len(), 12);
+            assert!(picked.iter().all(|&i| i < 32));
+            picked.sort_unstable();
+            picked.dedup();
+            assert_eq!(picked.len(), 12);
+        }
+    }
+
+    #[test]
+    fn partial_pick_is_a_prefix_of_the_full_shuffle() {
+        let full = weighted_shuffle(7, 20, 20);
+        assert_eq!(weighted_shuffle(7, 20, 5), full[..5]);
+    }
+
+    #[test]
+    fn weighted_sample_is_reproducible() {
+        assert_eq!(weighted_sample(9, &[1, 1, 1, 1], 4), weighted_sample(9, &[1, 1, 1, 1], 4));
+        assert_eq!(weighted_sample(9, &[5, 1, 3], 3).len(), 3);
+    }
+
+    #[test]
+    fn zero_weights_are_never_drawn() {
+        for seed in 0..64 {
+            let drawn = weighted_sample(seed, &[0, 4, 0, 7, 1], 5);
+            assert_eq!(drawn.len(), 3);
+            assert!(!drawn.contains(&0) && !drawn.contains(&2));
+        }
+    }
+
+    #[test]
+    fn heavier_weights_are_drawn_first_more_often() {
+        let heavy_first = (0..1000)
+            .filter(|&seed| weighted_sample(seed, &[1, 9], 1) == vec![1])
+            .count();
+        assert!(heavy_first > 800, "heavy agent drawn first {} times", heavy_first);
+    }
+
+    #[test]
+    fn bucket_seeds_differ_per_category() {
+        assert_ne!(bucket_seed(42, 0), bucket_seed(42, 1));
+        assert_eq!(bucket_seed(42, 3), bucket_seed(42, 3));
+    }
+
+    #[test]
+    fn pick_is_clamped_to_pool_len() {
+        assert_eq!(weighted_shuffle(1, 3, 10).len(), 3);
+        assert!(weighted_shuffle(1, 0, 4).is_empty());
+    }
+}
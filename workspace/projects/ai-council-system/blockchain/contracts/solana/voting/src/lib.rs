@@ -1,6 +1,16 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::TokenAccount;
+use council_selection::{AgentProfile, MAX_REPUTATION};
 
-declare_id!("Voting11111111111111111111111111111111111");
+declare_id!("5x8gXdpiRUt4ooYfmnnscFrWzsmaiWZVDMQHZsY9DNEH");
 
 #[program]
 pub mod voting {
@@ -11,24 +21,261 @@ pub mod voting {
         ctx: Context<InitializeDebate>,
         debate_id: String,
         topic: String,
-        max_rounds: u8,
+        config: DebateConfig,
     ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        require!(
+            !debate_id.is_empty(),
+            ErrorCode::EmptyDebateId
+        );
+
+        require!(
+            debate_id.len() <= MAX_DEBATE_ID_LEN,
+            ErrorCode::DebateIdTooLong
+        );
+
+        require!(
+            topic.len() <= MAX_TOPIC_LEN,
+            ErrorCode::TopicTooLong
+        );
+
+        require!(
+            config.voting_duration_seconds > 0,
+            ErrorCode::InvalidVotingDuration
+        );
+
+        require!(
+            config.max_rounds <= MAX_ROUNDS,
+            ErrorCode::InvalidMaxRounds
+        );
+
+        require!(
+            config.min_confidence <= 100,
+            ErrorCode::InvalidConfidence
+        );
+
+        require!(
+            config.registered_agents.len() <= MAX_REGISTERED_AGENTS
+                && config
+                    .registered_agents
+                    .iter()
+                    .all(|a| a.agent_id.len() <= MAX_AGENT_ID_LEN),
+            ErrorCode::InvalidAgentRegistry
+        );
+
+        require!(
+            config.confidence_budget == 0 || !config.registered_agents.is_empty(),
+            ErrorCode::InvalidConfidenceBudget
+        );
+
+        require!(
+            config.tally_cooldown_seconds >= 0,
+            ErrorCode::InvalidTallyCooldown
+        );
+
+        require!(
+            config.dispute_window_seconds >= 0,
+            ErrorCode::InvalidDisputeWindow
+        );
+
+        require!(
+            config.reveal_window_seconds >= 0,
+            ErrorCode::InvalidRevealWindow
+        );
+
+        require!(
+            config.min_vote_interval_seconds >= 0,
+            ErrorCode::InvalidVoteInterval
+        );
+
+        require!(
+            config.min_vote_interval_seconds == 0 || !config.registered_agents.is_empty(),
+            ErrorCode::InvalidVoteInterval
+        );
+
+        require!(
+            config.vote_ttl_seconds >= 0,
+            ErrorCode::InvalidVoteTtl
+        );
+
+        require!(
+            config.threshold_bps <= WEIGHT_SCALE_BPS as u16,
+            ErrorCode::InvalidThreshold
+        );
+
+        require!(
+            config.max_weight_bps <= WEIGHT_SCALE_BPS as u16,
+            ErrorCode::InvalidWeightCap
+        );
+
+        require!(
+            config.neutral_discount <= 100,
+            ErrorCode::InvalidNeutralDiscount
+        );
+
+        require!(
+            config
+                .outcome_action
+                .as_ref()
+                .map_or(true, |a| !a.is_empty() && a.len() <= MAX_OUTCOME_ACTION_LEN),
+            ErrorCode::InvalidOutcomeAction
+        );
+
+        require!(
+            if config.recency_weighting {
+                config.round_multipliers.len() == config.max_rounds as usize
+                    && config.round_multipliers.len() <= MAX_ROUND_MULTIPLIERS
+            } else {
+                config.round_multipliers.is_empty()
+            },
+            ErrorCode::InvalidRoundMultipliers
+        );
+
+        require!(
+            config.topic_hash != Some([0u8; 32]),
+            ErrorCode::InvalidTopicHash
+        );
+
+        require!(
+            config.ranked_options.is_empty()
+                || (config.ranked_options.len() >= 2
+                    && config.ranked_options.len() <= MAX_RANKED_OPTIONS
+                    && config.ranked_options.iter().all(|o| o.len() <= 32)),
+            ErrorCode::InvalidRankedOptions
+        );
+
+        require!(
+            config.custom_options.is_empty()
+                || (config.custom_options.len() >= 2
+                    && config.custom_options.len() <= MAX_CUSTOM_OPTIONS
+                    && config.custom_options.iter().all(|o| !o.is_empty() && o.len() <= 32)),
+            ErrorCode::InvalidCustomOptions
+        );
+
+        require!(
+            !config.normalized || config.score_scale == ScoreScale::BasisPoints,
+            ErrorCode::NormalizedNeedsBasisPoints
+        );
+
+        require!(
+            config.sub_questions.len() <= MAX_SUB_QUESTIONS
+                && config
+                    .sub_questions
+                    .iter()
+                    .all(|q| !q.is_empty() && q.len() <= MAX_SUB_QUESTION_LEN),
+            ErrorCode::InvalidSubQuestions
+        );
+
+        // Anti-spam bond, held in the debate account until `reclaim_bond`
+        if config.bond_lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: ctx.accounts.debate.to_account_info(),
+                    },
+                ),
+                config.bond_lamports,
+            )?;
+        }
+
         let debate = &mut ctx.accounts.debate;
         debate.debate_id = debate_id;
         debate.topic = topic;
+        debate.topic_hash = config.topic_hash.unwrap_or_default();
         debate.authority = ctx.accounts.authority.key();
-        debate.max_rounds = max_rounds;
+        debate.max_rounds = config.max_rounds;
         debate.current_round = 0;
         debate.votes = Vec::new();
         debate.timestamp = Clock::get()?.unix_timestamp;
+        debate.deadline = debate
+            .timestamp
+            .checked_add(config.voting_duration_seconds)
+            .ok_or(ErrorCode::InvalidVotingDuration)?;
         debate.status = DebateStatus::Active;
+        debate.tally_mode = config.tally_mode;
+        debate.quorum = config.quorum;
+        debate.stake_mint = config.stake_mint;
+        debate.tie_break = config.tie_break;
+        debate.contested_threshold = config.contested_threshold;
+        debate.min_confidence = config.min_confidence;
+        debate.registered_agents = config.registered_agents;
+        for agent in debate.registered_agents.iter_mut() {
+            agent.confidence_spent = 0;
+            agent.relay_nonce = 0;
+            agent.last_action = 0;
+        }
+        debate.confidence_budget = config.confidence_budget;
+        debate.commit_reveal = config.commit_reveal;
+        debate.truncate_reasoning = config.truncate_reasoning;
+        debate.neutral_discount = config.neutral_discount;
+        debate.tally_cooldown_seconds = config.tally_cooldown_seconds;
+        debate.last_vote_timestamp = 0;
+        debate.dispute_window_seconds = config.dispute_window_seconds;
+        debate.disputes = Vec::new();
+        debate.threshold_bps = config.threshold_bps;
+        debate.consensus_reached = false;
+        debate.no_consensus = false;
+        debate.max_weight_bps = config.max_weight_bps;
+        debate.recency_weighting = config.recency_weighting;
+        debate.round_multipliers = config.round_multipliers;
+        debate.round_results = Vec::new();
+        debate.unrevealed_commits = 0;
+        debate.abstain_counts_for_quorum = config.abstain_counts_for_quorum;
+        debate.close_reason = None;
+        debate.avg_confidence = 0;
+        debate.median_confidence = 0;
+        debate.reveal_deposit_lamports = config.reveal_deposit_lamports;
+        debate.reveal_window_seconds = config.reveal_window_seconds;
+        debate.reveal_deadline = 0;
+        debate.forfeited_lamports = 0;
+        debate.custom_options = config.custom_options;
+        debate.custom_scores = Vec::new();
+        debate.consensus_entropy = 0;
+        debate.normalized = config.normalized;
+        debate.result_mode = config.result_mode;
+        debate.allocations = Vec::new();
+        debate.sub_questions = config.sub_questions;
+        debate.question_results = Vec::new();
+        debate.min_vote_interval_seconds = config.min_vote_interval_seconds;
+        debate.vote_ttl_seconds = config.vote_ttl_seconds;
+        debate.outcome_action = config.outcome_action;
+        debate.bond_lamports = config.bond_lamports;
+        debate.bond_reclaimed = false;
+        debate.vote_conditions = Vec::new();
+        debate.minority_report = None;
+        debate.score_scale = config.score_scale;
         debate.votes_tallied = false;
+        debate.ranked_options = config.ranked_options;
+        debate.ranked_ballot_count = 0;
+        debate.elimination_order = Vec::new();
+        debate.ranked_winner = None;
+        debate.delegations = Vec::new();
+        debate.oracle_context = Vec::new();
+
+        ctx.accounts.authority_registry.record(debate.debate_id.clone());
+
+        emit!(DebateInitialized {
+            debate_id: debate.debate_id.clone(),
+            topic: debate.topic.clone(),
+            authority: debate.authority,
+            deadline: debate.deadline,
+        });
 
         msg!("Debate initialized: {}", debate.debate_id);
         Ok(())
     }
 
     /// Record a vote on-chain
+    ///
+    /// Every vote path takes the agent's council_selection `AgentProfile`,
+    /// which must exist and be keyed to the voter; its reputation (capped at
+    /// `MAX_REPUTATION`) replaces the debate registry's.
     pub fn cast_vote(
         ctx: Context<CastVote>,
         agent_id: String,
@@ -36,6 +283,11 @@ pub mod voting {
         confidence: u8,
         reasoning: String,
     ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
         let debate = &mut ctx.accounts.debate;
 
         require!(
@@ -44,202 +296,4548 @@ pub mod voting {
         );
 
         require!(
-            confidence <= 100,
-            ErrorCode::InvalidConfidence
+            !debate.commit_reveal,
+            ErrorCode::CommitRevealRequired
         );
 
-        // Check if agent already voted
-        let existing_vote = debate.votes.iter().find(|v| v.agent_id == agent_id);
-        require!(existing_vote.is_none(), ErrorCode::AlreadyVoted);
-
-        let vote = Vote {
-            agent_id: agent_id.clone(),
-            vote_option,
-            confidence,
-            reasoning: reasoning.clone(),
-            timestamp: Clock::get()?.unix_timestamp,
-        };
-
-        debate.votes.push(vote);
-
-        msg!(
-            "Vote cast by agent: {}, option: {:?}, confidence: {}",
-            agent_id,
-            vote_option,
-            confidence
+        require!(
+            Clock::get()?.unix_timestamp <= debate.deadline,
+            ErrorCode::VotingClosed
         );
 
-        Ok(())
+        let voter = ctx.accounts.voter.key();
+        authorize_voter(debate, &agent_id, &voter)?;
+        let stake_weight = stake_weight_of(debate, &voter, &ctx.accounts.voter_stake)?;
+
+        record_vote(
+            debate,
+            voter,
+            stake_weight,
+            VoteInput {
+                agent_id,
+                vote_option,
+                confidence,
+                reasoning,
+            },
+        )?;
+        apply_agent_profile(debate, &ctx.accounts.agent_profile, &voter)
     }
 
-    /// Tally votes and determine outcome
-    pub fn tally_votes(
-        ctx: Context<TallyVotes>,
+    /// Submit a vote signed off-chain by the agent, paid for by a relayer
+    ///
+    /// The relayer must be in the debate's `RelayerSet`. The instruction
+    /// immediately before this one must be an Ed25519 program instruction
+    /// verifying `agent_signature` by the agent's registered key over
+    /// `relayed_vote_message`; the runtime rejects the transaction if that
+    /// signature is invalid. Relayed votes require an agent registry.
+    ///
+    /// The message binds the agent's current `relay_nonce`, which each
+    /// accepted relay advances, so a signed payload cannot be replayed, e.g.
+    /// to restore a vote the agent has since retracted.
+    pub fn cast_vote_relayed(
+        ctx: Context<CastVoteRelayed>,
+        agent_id: String,
+        vote_option: VoteOption,
+        confidence: u8,
+        reasoning: String,
+        agent_signature: [u8; 64],
     ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate_key = ctx.accounts.debate.key();
         let debate = &mut ctx.accounts.debate;
 
+        require!(
+            ctx.accounts
+                .relayer_set
+                .relayers
+                .contains(&ctx.accounts.relayer.key()),
+            ErrorCode::UnauthorizedRelayer
+        );
+
         require!(
             debate.status == DebateStatus::Active,
             ErrorCode::DebateNotActive
         );
 
         require!(
-            debate.votes.len() > 0,
-            ErrorCode::NoVotes
+            !debate.commit_reveal,
+            ErrorCode::CommitRevealRequired
         );
 
-        // Calculate weighted votes
-        let mut support_score: f64 = 0.0;
-        let mut oppose_score: f64 = 0.0;
-        let mut neutral_score: f64 = 0.0;
+        require!(
+            Clock::get()?.unix_timestamp <= debate.deadline,
+            ErrorCode::VotingClosed
+        );
 
-        for vote in &debate.votes {
-            let weight = vote.confidence as f64 / 100.0;
-            match vote.vote_option {
-                VoteOption::Support => support_score += weight,
-                VoteOption::Oppose => oppose_score += weight,
-                VoteOption::Neutral => neutral_score += weight,
-                VoteOption::Abstain => {},
-            }
-        }
+        let (agent_key, nonce) = debate
+            .registered_agents
+            .iter()
+            .find(|a| a.agent_id == agent_id)
+            .map(|a| (a.key, a.relay_nonce))
+            .ok_or(ErrorCode::AgentKeyMismatch)?;
 
-        // Determine winner
-        let outcome = if support_score > oppose_score && support_score > neutral_score {
-            VoteOption::Support
-        } else if oppose_score > support_score && oppose_score > neutral_score {
-            VoteOption::Oppose
-        } else {
-            VoteOption::Neutral
-        };
+        let instructions = &ctx.accounts.instructions;
+        let current = load_current_index_checked(instructions)? as usize;
+        require!(
+            current > 0,
+            ErrorCode::InvalidAgentSignature
+        );
+        let verify_ix = load_instruction_at_checked(current - 1, instructions)?;
+        let message = relayed_vote_message(
+            &debate_key,
+            &agent_id,
+            vote_option,
+            confidence,
+            &reasoning,
+            debate.current_round,
+            nonce,
+        );
+        require!(
+            ed25519_verifies(&verify_ix, &agent_key, &agent_signature, &message),
+            ErrorCode::InvalidAgentSignature
+        );
 
-        debate.outcome = Some(outcome);
-        debate.support_score = (support_score * 100.0) as u16;
-        debate.oppose_score = (oppose_score * 100.0) as u16;
-        debate.neutral_score = (neutral_score * 100.0) as u16;
-        debate.votes_tallied = true;
-        debate.status = DebateStatus::Completed;
-        debate.completion_timestamp = Clock::get()?.unix_timestamp;
+        authorize_voter(debate, &agent_id, &agent_key)?;
+        let stake_weight = stake_weight_of(debate, &agent_key, &ctx.accounts.voter_stake)?;
 
-        msg!(
-            "Votes tallied - Support: {}, Oppose: {}, Neutral: {}, Outcome: {:?}",
-            debate.support_score,
-            debate.oppose_score,
-            debate.neutral_score,
-            debate.outcome
-        );
+        record_vote(
+            debate,
+            agent_key,
+            stake_weight,
+            VoteInput {
+                agent_id: agent_id.clone(),
+                vote_option,
+                confidence,
+                reasoning,
+            },
+        )?;
+        apply_agent_profile(debate, &ctx.accounts.agent_profile, &agent_key)?;
 
+        if let Some(agent) = debate.registered_agents.iter_mut().find(|a| a.agent_id == agent_id) {
+            agent.relay_nonce = nonce.checked_add(1).ok_or(ErrorCode::RelayNonceExhausted)?;
+        }
         Ok(())
     }
 
-    /// Close a debate (emergency stop)
-    pub fn close_debate(
-        ctx: Context<CloseDebate>,
+    /// Cast a vote whose reasoning stays off-chain
+    ///
+    /// Only `reasoning_hash` is stored, hex-encoded in `reasoning` with
+    /// `reasoning_is_hash` set, so the plaintext can later be checked against it.
+    pub fn cast_vote_private(
+        ctx: Context<CastVote>,
+        agent_id: String,
+        vote_option: VoteOption,
+        confidence: u8,
+        reasoning_hash: [u8; 32],
     ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
         let debate = &mut ctx.accounts.debate;
-        debate.status = DebateStatus::Closed;
 
-        msg!("Debate closed: {}", debate.debate_id);
-        Ok(())
-    }
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
 
-    /// Get vote results
-    pub fn get_results(
-        ctx: Context<GetResults>,
-    ) -> Result<VoteResults> {
-        let debate = &ctx.accounts.debate;
+        require!(
+            !debate.commit_reveal,
+            ErrorCode::CommitRevealRequired
+        );
 
         require!(
-            debate.votes_tallied,
-            ErrorCode::VotesNotTallied
+            Clock::get()?.unix_timestamp <= debate.deadline,
+            ErrorCode::VotingClosed
         );
 
-        Ok(VoteResults {
-            debate_id: debate.debate_id.clone(),
-            outcome: debate.outcome.unwrap(),
-            support_score: debate.support_score,
-            oppose_score: debate.oppose_score,
-            neutral_score: debate.neutral_score,
-            total_votes: debate.votes.len() as u16,
-        })
+        let voter = ctx.accounts.voter.key();
+        authorize_voter(debate, &agent_id, &voter)?;
+        let stake_weight = stake_weight_of(debate, &voter, &ctx.accounts.voter_stake)?;
+
+        record_vote(
+            debate,
+            voter,
+            stake_weight,
+            VoteInput {
+                agent_id,
+                vote_option,
+                confidence,
+                reasoning: reasoning_hash.iter().map(|b| format!("{:02x}", b)).collect(),
+            },
+        )?;
+        apply_agent_profile(debate, &ctx.accounts.agent_profile, &voter)?;
+
+        // record_vote just pushed this vote; 64 hex chars always fit untruncated
+        if let Some(vote) = debate.votes.last_mut() {
+            vote.reasoning_is_hash = true;
+        }
+        Ok(())
     }
-}
 
-#[derive(Accounts)]
-#[instruction(debate_id: String)]
-pub struct InitializeDebate<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Debate::INIT_SPACE,
-        seeds = [b"debate", debate_id.as_bytes()],
-        bump
-    )]
-    pub debate: Account<'info, Debate>,
+    /// Cast a Support vote that only counts if another debate ends with
+    /// `required_outcome`
+    ///
+    /// The vote is resolved at tally time against the `depends_on` debate
+    /// account (see `tally_votes`): it counts as Support on a match and as
+    /// Abstain otherwise.
+    pub fn cast_conditional_vote(
+        ctx: Context<CastVote>,
+        agent_id: String,
+        confidence: u8,
+        reasoning: String,
+        depends_on: Pubkey,
+        required_outcome: VoteOption,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+        let debate = &mut ctx.accounts.debate;
 
-    pub system_program: Program<'info, System>,
-}
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
 
-#[derive(Accounts)]
-pub struct CastVote<'info> {
-    #[account(mut)]
-    pub debate: Account<'info, Debate>,
+        require!(
+            !debate.commit_reveal,
+            ErrorCode::CommitRevealRequired
+        );
 
-    pub voter: Signer<'info>,
-}
+        require!(
+            Clock::get()?.unix_timestamp <= debate.deadline,
+            ErrorCode::VotingClosed
+        );
 
-#[derive(Accounts)]
-pub struct TallyVotes<'info> {
-    #[account(mut, has_one = authority)]
-    pub debate: Account<'info, Debate>,
+        require!(
+            required_outcome != VoteOption::Abstain && depends_on != debate.key(),
+            ErrorCode::InvalidVoteCondition
+        );
 
-    pub authority: Signer<'info>,
-}
+        let condition = VoteCondition {
+            depends_on,
+            required_outcome,
+        };
+        let index = match debate.vote_conditions.iter().position(|c| *c == condition) {
+            Some(index) => index,
+            None => {
+                require!(
+                    debate.vote_conditions.len() < MAX_VOTE_CONDITIONS,
+                    ErrorCode::VoteConditionsFull
+                );
+                debate.vote_conditions.push(condition);
+                debate.vote_conditions.len() - 1
+            }
+        };
 
-#[derive(Accounts)]
-pub struct CloseDebate<'info> {
-    #[account(mut, has_one = authority)]
-    pub debate: Account<'info, Debate>,
+        let voter = ctx.accounts.voter.key();
+        authorize_voter(debate, &agent_id, &voter)?;
+        let stake_weight = stake_weight_of(debate, &voter, &ctx.accounts.voter_stake)?;
 
-    pub authority: Signer<'info>,
-}
+        record_vote(
+            debate,
+            voter,
+            stake_weight,
+            VoteInput {
+                agent_id,
+                vote_option: VoteOption::Support,
+                confidence,
+                reasoning,
+            },
+        )?;
+        apply_agent_profile(debate, &ctx.accounts.agent_profile, &voter)?;
 
-#[derive(Accounts)]
-pub struct GetResults<'info> {
-    pub debate: Account<'info, Debate>,
-}
+        // record_vote just pushed this vote
+        if let Some(vote) = debate.votes.last_mut() {
+            vote.condition = Some(index as u8);
+        }
+        Ok(())
+    }
 
-#[account]
-pub struct Debate {
-    pub debate_id: String,            // 32 bytes (max)
-    pub topic: String,                 // 128 bytes (max)
-    pub authority: Pubkey,             // 32 bytes
-    pub max_rounds: u8,                // 1 byte
-    pub current_round: u8,             // 1 byte
-    pub votes: Vec<Vote>,              // Dynamic (max 20 votes * ~200 bytes = 4000 bytes)
-    pub timestamp: i64,                // 8 bytes
-    pub completion_timestamp: i64,     // 8 bytes
-    pub status: DebateStatus,          // 1 byte
-    pub outcome: Option<VoteOption>,   // 2 bytes
-    pub support_score: u16,            // 2 bytes
-    pub oppose_score: u16,             // 2 bytes
-    pub neutral_score: u16,            // 2 bytes
-    pub votes_tallied: bool,           // 1 byte
-}
+    /// Vote for one of the debate's `custom_options` by index
+    ///
+    /// Custom votes are tallied into `custom_scores` as their own electorate
+    /// and stay out of the main question's scores, quorum and confidence
+    /// statistics. They are stored with a Neutral placeholder option and still
+    /// count toward `participant_count`.
+    pub fn cast_custom_vote(
+        ctx: Context<CastVote>,
+        agent_id: String,
+        option_index: u8,
+        confidence: u8,
+        reasoning: String,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
 
-impl Debate {
-    pub const INIT_SPACE: usize = 32 + 128 + 32 + 1 + 1 + (4 + 4000) + 8 + 8 + 1 + 2 + 2 + 2 + 2 + 1;
-}
+        let debate = &mut ctx.accounts.debate;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct Vote {
-    pub agent_id: String,              // 32 bytes (max)
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        require!(
+            !debate.commit_reveal,
+            ErrorCode::CommitRevealRequired
+        );
+
+        require!(
+            Clock::get()?.unix_timestamp <= debate.deadline,
+            ErrorCode::VotingClosed
+        );
+
+        require!(
+            (option_index as usize) < debate.custom_options.len(),
+            ErrorCode::InvalidCustomOption
+        );
+
+        let voter = ctx.accounts.voter.key();
+        authorize_voter(debate, &agent_id, &voter)?;
+        let stake_weight = stake_weight_of(debate, &voter, &ctx.accounts.voter_stake)?;
+
+        record_vote(
+            debate,
+            voter,
+            stake_weight,
+            VoteInput {
+                agent_id,
+                vote_option: VoteOption::Neutral,
+                confidence,
+                reasoning,
+            },
+        )?;
+        apply_agent_profile(debate, &ctx.accounts.agent_profile, &voter)?;
+
+        // record_vote just pushed this vote
+        if let Some(vote) = debate.votes.last_mut() {
+            vote.custom_option = Some(option_index);
+        }
+        Ok(())
+    }
+
+    /// Vote on one of the debate's `sub_questions` by index
+    ///
+    /// Each sub-question is scored on its own at tally (see
+    /// `get_question_results`); its votes stay out of the main question's
+    /// scores, counts and quorum.
+    pub fn cast_vote_on(
+        ctx: Context<CastVote>,
+        agent_id: String,
+        question_index: u8,
+        vote_option: VoteOption,
+        confidence: u8,
+        reasoning: String,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        require!(
+            !debate.commit_reveal,
+            ErrorCode::CommitRevealRequired
+        );
+
+        require!(
+            Clock::get()?.unix_timestamp <= debate.deadline,
+            ErrorCode::VotingClosed
+        );
+
+        require!(
+            (question_index as usize) < debate.sub_questions.len(),
+            ErrorCode::InvalidQuestionIndex
+        );
+
+        let voter = ctx.accounts.voter.key();
+        authorize_voter(debate, &agent_id, &voter)?;
+        let stake_weight = stake_weight_of(debate, &voter, &ctx.accounts.voter_stake)?;
+
+        record_vote_on(
+            debate,
+            voter,
+            stake_weight,
+            Some(question_index),
+            VoteInput {
+                agent_id,
+                vote_option,
+                confidence,
+                reasoning,
+            },
+        )?;
+        apply_agent_profile(debate, &ctx.accounts.agent_profile, &voter)
+    }
+
+    /// Record several votes relayed by the debate authority in one call
+    ///
+    /// Each agent co-signs the transaction: the remaining accounts hold, for
+    /// each entry of `votes` in order, the agent's key as a signer and its
+    /// council_selection `AgentProfile`. The key is checked against the
+    /// registry and profile like `cast_vote`, and is recorded as the vote's
+    /// voter. Any
+    /// invalid entry fails the whole batch. Not available for stake-weighted
+    /// or commit-reveal debates, whose votes need per-voter accounts.
+    pub fn batch_cast_votes(
+        ctx: Context<BatchCastVotes>,
+        votes: Vec<VoteInput>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        require!(
+            !debate.commit_reveal,
+            ErrorCode::CommitRevealRequired
+        );
+
+        require!(
+            debate.stake_mint.is_none(),
+            ErrorCode::StakeAccountRequired
+        );
+
+        require!(
+            !votes.is_empty() && votes.len() <= MAX_BATCH_VOTES,
+            ErrorCode::InvalidBatchSize
+        );
+
+        require!(
+            Clock::get()?.unix_timestamp <= debate.deadline,
+            ErrorCode::VotingClosed
+        );
+
+        let agents = ctx.remaining_accounts;
+        require!(
+            agents.len() == 2 * votes.len(),
+            ErrorCode::AgentSignerRequired
+        );
+
+        let count = votes.len();
+        for (input, accounts) in votes.into_iter().zip(agents.chunks(2)) {
+            let (signer, profile) = (&accounts[0], &accounts[1]);
+            require!(
+                signer.is_signer,
+                ErrorCode::AgentSignerRequired
+            );
+            require_keys_eq!(
+                profile.key(),
+                agent_profile_address(&input.agent_id),
+                ErrorCode::InvalidAgentProfile
+            );
+            authorize_voter(debate, &input.agent_id, signer.key)?;
+            record_vote(debate, signer.key(), 1, input)?;
+            apply_agent_profile(debate, profile, signer.key)?;
+        }
+
+        msg!("Batch accepted {} votes for debate: {}", count, debate.debate_id);
+        Ok(())
+    }
+
+    /// Commit to a vote without revealing it. `commitment` is
+    /// `hash(vote_option as u8, confidence, nonce)` (see `vote_commitment`).
+    pub fn commit_vote(
+        ctx: Context<CommitVote>,
+        agent_id: String,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        require!(
+            debate.commit_reveal,
+            ErrorCode::CommitRevealDisabled
+        );
+
+        require!(
+            debate.is_allowed(&ctx.accounts.voter.key()),
+            ErrorCode::UnauthorizedVoter
+        );
+
+        require!(
+            debate.agent_key_matches(&agent_id, &ctx.accounts.voter.key()),
+            ErrorCode::AgentKeyMismatch
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now <= debate.deadline,
+            ErrorCode::VotingClosed
+        );
+
+        // Anti-griefing deposit, held in the commitment until it is revealed
+        // or swept
+        let deposit = debate.reveal_deposit_lamports;
+        if deposit > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.voter.to_account_info(),
+                        to: ctx.accounts.commitment.to_account_info(),
+                    },
+                ),
+                deposit,
+            )?;
+        }
+
+        let record = &mut ctx.accounts.commitment;
+        record.debate = debate.key();
+        record.agent_id = agent_id;
+        record.voter = ctx.accounts.voter.key();
+        record.commitment = commitment;
+        record.timestamp = now;
+        record.deposit = deposit;
+        debate.unrevealed_commits += 1;
+
+        msg!("Vote committed by agent: {}", record.agent_id);
+        Ok(())
+    }
+
+    /// Move a commit-reveal debate from commitments to reveals once the
+    /// voting deadline has passed, so no vote is opened while others can
+    /// still commit
+    pub fn begin_reveal(ctx: Context<BeginReveal>) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.commit_reveal,
+            ErrorCode::CommitRevealDisabled
+        );
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::InvalidDebateStatus
+        );
+
+        require!(
+            Clock::get()?.unix_timestamp > debate.deadline,
+            ErrorCode::DeadlineNotReached
+        );
+
+        debate.status = DebateStatus::Revealing;
+        let now = Clock::get()?.unix_timestamp;
+        debate.reveal_deadline = if debate.reveal_window_seconds > 0 {
+            now.saturating_add(debate.reveal_window_seconds)
+        } else {
+            i64::MAX
+        };
+
+        msg!(
+            "Reveal phase started for debate: {} ({} commitments)",
+            debate.debate_id,
+            debate.unrevealed_commits
+        );
+        Ok(())
+    }
+
+    /// Open a commitment and record the vote it hides. The commitment account
+    /// is closed and its rent and deposit returned to the voter.
+    pub fn reveal_vote(
+        ctx: Context<RevealVote>,
+        agent_id: String,
+        vote_option: VoteOption,
+        confidence: u8,
+        reasoning: String,
+        nonce: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        require!(
+            ctx.accounts.debate.status == DebateStatus::Revealing,
+            ErrorCode::InvalidDebateStatus
+        );
+
+        require!(
+            Clock::get()?.unix_timestamp <= ctx.accounts.debate.reveal_deadline,
+            ErrorCode::RevealWindowClosed
+        );
+
+        require!(
+            vote_commitment(vote_option, confidence, &nonce) == ctx.accounts.commitment.commitment,
+            ErrorCode::CommitmentMismatch
+        );
+
+        let debate = &mut ctx.accounts.debate;
+        let voter = ctx.accounts.voter.key();
+        authorize_voter(debate, &agent_id, &voter)?;
+        let stake_weight = stake_weight_of(debate, &voter, &ctx.accounts.voter_stake)?;
+
+        record_vote(
+            debate,
+            voter,
+            stake_weight,
+            VoteInput {
+                agent_id,
+                vote_option,
+                confidence,
+                reasoning,
+            },
+        )?;
+        apply_agent_profile(debate, &ctx.accounts.agent_profile, &voter)?;
+        debate.unrevealed_commits = debate.unrevealed_commits.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Forfeit the deposit of a commitment that was never revealed
+    ///
+    /// Callable by anyone once reveals are over: past the reveal deadline, or
+    /// once the debate has left the commit and reveal phases. The deposit
+    /// moves to the debate account; the commitment's rent goes back to the voter.
+    pub fn sweep_unrevealed(
+        ctx: Context<SweepUnrevealed>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        let reveals_over = match debate.status {
+            DebateStatus::Active => false,
+            DebateStatus::Revealing => Clock::get()?.unix_timestamp > debate.reveal_deadline,
+            _ => true,
+        };
+        require!(
+            reveals_over,
+            ErrorCode::RevealWindowOpen
+        );
+
+        let deposit = ctx.accounts.commitment.deposit;
+        **ctx.accounts.commitment.to_account_info().try_borrow_mut_lamports()? -= deposit;
+        **debate.to_account_info().try_borrow_mut_lamports()? += deposit;
+        debate.forfeited_lamports = debate.forfeited_lamports.saturating_add(deposit);
+        debate.unrevealed_commits = debate.unrevealed_commits.saturating_sub(1);
+
+        msg!(
+            "Unrevealed commitment by agent {} swept, {} lamports forfeited",
+            ctx.accounts.commitment.agent_id,
+            deposit
+        );
+        Ok(())
+    }
+
+    /// Withdraw a previously cast vote while the debate is still open
+    pub fn retract_vote(
+        ctx: Context<RetractVote>,
+        agent_id: String,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        require!(
+            !debate.votes_tallied,
+            ErrorCode::VotesAlreadyTallied
+        );
+
+        let current_round = debate.current_round;
+        let index = debate
+            .votes
+            .iter()
+            .position(|v| v.agent_id == agent_id && v.round == current_round && v.question.is_none())
+            .ok_or(ErrorCode::VoteNotFound)?;
+
+        // Only the original signer may withdraw their vote
+        require_keys_eq!(
+            debate.votes[index].voter,
+            ctx.accounts.voter.key(),
+            ErrorCode::VoterMismatch
+        );
+
+        debate.rate_limit(&agent_id, Clock::get()?.unix_timestamp)?;
+
+        let retracted = debate.votes.remove(index);
+        debate.spend_confidence(&agent_id, retracted.confidence, 0)?;
+
+        msg!("Vote retracted by agent: {}", agent_id);
+
+        Ok(())
+    }
+
+    /// Update an existing vote in place, keeping its original timestamp
+    pub fn change_vote(
+        ctx: Context<ChangeVote>,
+        agent_id: String,
+        vote_option: VoteOption,
+        confidence: u8,
+        reasoning: String,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now <= debate.deadline,
+            ErrorCode::VotingClosed
+        );
+
+        require!(
+            confidence <= 100,
+            ErrorCode::InvalidConfidence
+        );
+
+        require!(
+            confidence >= debate.min_confidence,
+            ErrorCode::ConfidenceTooLow
+        );
+
+        let (reasoning, reasoning_truncated) = debate.fit_reasoning(reasoning)?;
+
+        let current_round = debate.current_round;
+        let index = debate
+            .votes
+            .iter()
+            .position(|v| v.agent_id == agent_id && v.round == current_round && v.question.is_none())
+            .ok_or(ErrorCode::VoteNotFound)?;
+
+        require_keys_eq!(
+            debate.votes[index].voter,
+            ctx.accounts.voter.key(),
+            ErrorCode::VoterMismatch
+        );
+
+        debate.rate_limit(&agent_id, now)?;
+
+        let vote = &mut debate.votes[index];
+        let previous_confidence = vote.confidence;
+        vote.vote_option = vote_option;
+        vote.confidence = confidence;
+        vote.reasoning = reasoning;
+        vote.reasoning_truncated = reasoning_truncated;
+        vote.reasoning_is_hash = false;
+        vote.condition = None;
+        vote.custom_option = None;
+        vote.last_updated = now;
+        debate.last_vote_timestamp = now;
+        debate.spend_confidence(&agent_id, previous_confidence, confidence)?;
+
+        msg!(
+            "Vote changed by agent: {}, option: {:?}, confidence: {}",
+            agent_id,
+            vote_option,
+            confidence
+        );
+
+        Ok(())
+    }
+
+    /// Re-affirm a vote unchanged, restarting its `vote_ttl_seconds` decay
+    pub fn refresh_vote(
+        ctx: Context<RefreshVote>,
+        agent_id: String,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now <= debate.deadline,
+            ErrorCode::VotingClosed
+        );
+
+        let current_round = debate.current_round;
+        let index = debate
+            .votes
+            .iter()
+            .position(|v| v.agent_id == agent_id && v.round == current_round && v.question.is_none())
+            .ok_or(ErrorCode::VoteNotFound)?;
+
+        require_keys_eq!(
+            debate.votes[index].voter,
+            ctx.accounts.voter.key(),
+            ErrorCode::VoterMismatch
+        );
+
+        debate.rate_limit(&agent_id, now)?;
+
+        debate.votes[index].last_updated = now;
+
+        msg!("Vote refreshed by agent: {}", agent_id);
+        Ok(())
+    }
+
+    /// Delegate an agent's voting weight to a peer
+    ///
+    /// Delegations chain: if A delegates to B and B to C, C's vote carries
+    /// both. Agents that delegate cannot vote themselves.
+    pub fn delegate_vote(
+        ctx: Context<DelegateVote>,
+        from_agent: String,
+        to_agent: String,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        require!(
+            debate.is_allowed(&ctx.accounts.delegator.key()),
+            ErrorCode::UnauthorizedVoter
+        );
+
+        require!(
+            debate.agent_key_matches(&from_agent, &ctx.accounts.delegator.key()),
+            ErrorCode::AgentKeyMismatch
+        );
+
+        require!(
+            from_agent.len() <= MAX_AGENT_ID_LEN && to_agent.len() <= MAX_AGENT_ID_LEN,
+            ErrorCode::AgentIdTooLong
+        );
+
+        require!(
+            from_agent != to_agent,
+            ErrorCode::SelfDelegation
+        );
+
+        require!(
+            !debate.delegations.iter().any(|d| d.from_agent == from_agent),
+            ErrorCode::AlreadyDelegated
+        );
+
+        require!(
+            !debate.votes.iter().any(|v| v.agent_id == from_agent),
+            ErrorCode::AlreadyVoted
+        );
+
+        require!(
+            debate.resolve_delegate(&to_agent) != from_agent,
+            ErrorCode::DelegationCycle
+        );
+
+        require!(
+            debate.delegations.len() < MAX_DELEGATIONS,
+            ErrorCode::DelegationsFull
+        );
+
+        debate.delegations.push(Delegation {
+            from_agent: from_agent.clone(),
+            to_agent: to_agent.clone(),
+            delegator: ctx.accounts.delegator.key(),
+        });
+
+        msg!("Vote delegated from agent: {} to agent: {}", from_agent, to_agent);
+
+        Ok(())
+    }
+
+    /// Record an ordered preference list over the debate's ranked options
+    pub fn cast_ranked_vote(
+        ctx: Context<CastRankedVote>,
+        agent_id: String,
+        rankings: Vec<u8>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        require!(
+            debate.is_allowed(&ctx.accounts.voter.key()),
+            ErrorCode::UnauthorizedVoter
+        );
+
+        require!(
+            debate.agent_key_matches(&agent_id, &ctx.accounts.voter.key()),
+            ErrorCode::AgentKeyMismatch
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now <= debate.deadline,
+            ErrorCode::VotingClosed
+        );
+
+        require!(
+            !debate.ranked_options.is_empty(),
+            ErrorCode::RankedVotingDisabled
+        );
+
+        require!(
+            agent_id.len() <= MAX_AGENT_ID_LEN,
+            ErrorCode::AgentIdTooLong
+        );
+
+        // Rankings list distinct option indices, most preferred first
+        let option_count = debate.ranked_options.len();
+        let mut seen = [false; MAX_RANKED_OPTIONS];
+        require!(
+            !rankings.is_empty() && rankings.len() <= option_count,
+            ErrorCode::InvalidRankings
+        );
+        for &option in &rankings {
+            let option = option as usize;
+            require!(
+                option < option_count && !seen[option],
+                ErrorCode::InvalidRankings
+            );
+            seen[option] = true;
+        }
+
+        // A second ballot from the same agent fails when its PDA is created
+        require!(
+            (debate.ranked_ballot_count as usize) < MAX_RANKED_BALLOTS,
+            ErrorCode::RankedBallotsFull
+        );
+        debate.ranked_ballot_count += 1;
+
+        let ballot = &mut ctx.accounts.ballot;
+        ballot.debate = debate.key();
+        ballot.agent_id = agent_id.clone();
+        ballot.voter = ctx.accounts.voter.key();
+        ballot.rankings = rankings;
+        ballot.timestamp = now;
+
+        msg!("Ranked ballot cast by agent: {}", agent_id);
+
+        Ok(())
+    }
+
+    /// Run instant-runoff elimination over the ranked ballots
+    ///
+    /// Each round counts every ballot toward its highest-ranked option that is
+    /// still in the running. An option holding more than half of the counted
+    /// ballots wins; otherwise the option with the fewest ballots is eliminated
+    /// (ties eliminate the highest option index) and the count repeats.
+    /// Ballots whose choices are all eliminated are exhausted and stop counting.
+    ///
+    /// Every ballot account cast in the debate must be passed in
+    /// `remaining_accounts`.
+    pub fn tally_ranked(
+        ctx: Context<TallyRanked>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate_key = ctx.accounts.debate.key();
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        require!(
+            debate.ranked_ballot_count > 0,
+            ErrorCode::NoVotes
+        );
+
+        let ballots = load_ranked_ballots(&debate_key, debate.ranked_ballot_count, ctx.remaining_accounts)?;
+
+        let option_count = debate.ranked_options.len();
+        let mut eliminated = [false; MAX_RANKED_OPTIONS];
+        let mut elimination_order = Vec::new();
+
+        let winner = loop {
+            let mut counts = [0u16; MAX_RANKED_OPTIONS];
+            let mut counted: u16 = 0;
+            for ballot in &ballots {
+                if let Some(&choice) = ballot
+                    .rankings
+                    .iter()
+                    .find(|&&o| !eliminated[o as usize])
+                {
+                    counts[choice as usize] += 1;
+                    counted += 1;
+                }
+            }
+
+            let remaining: Vec<usize> = (0..option_count).filter(|&o| !eliminated[o]).collect();
+            let leader = remaining
+                .iter()
+                .copied()
+                .max_by_key(|&o| (counts[o], std::cmp::Reverse(o)))
+                .ok_or(ErrorCode::NoVotes)?;
+
+            if remaining.len() == 1 || counts[leader] as u32 * 2 > counted as u32 {
+                break leader as u8;
+            }
+
+            let loser = remaining
+                .iter()
+                .copied()
+                .min_by_key(|&o| (counts[o], std::cmp::Reverse(o)))
+                .ok_or(ErrorCode::NoVotes)?;
+            eliminated[loser] = true;
+            elimination_order.push(loser as u8);
+        };
+
+        debate.ranked_winner = Some(winner);
+        debate.elimination_order = elimination_order;
+        debate.status = DebateStatus::Completed;
+        debate.completion_timestamp = Clock::get()?.unix_timestamp;
+
+        msg!(
+            "Ranked ballots tallied - Winner: {}, Eliminated: {:?}",
+            debate.ranked_options[winner as usize],
+            debate.elimination_order
+        );
+
+        Ok(())
+    }
+
+    /// Find the option that beats every other option head to head, if any
+    ///
+    /// A ballot prefers `a` over `b` when it ranks `a` above `b`, or ranks `a`
+    /// and leaves `b` unranked. Compare with `ranked_winner` to flag debates
+    /// where instant-runoff and Condorcet disagree.
+    ///
+    /// Every ballot account cast in the debate must be passed in
+    /// `remaining_accounts`.
+    pub fn find_condorcet_winner(
+        ctx: Context<FindCondorcetWinner>,
+    ) -> Result<Option<u8>> {
+        let debate = &ctx.accounts.debate;
+
+        require!(
+            debate.ranked_ballot_count > 0,
+            ErrorCode::NoVotes
+        );
+
+        let ballots = load_ranked_ballots(&debate.key(), debate.ranked_ballot_count, ctx.remaining_accounts)?;
+        Ok(condorcet_winner(debate.ranked_options.len(), &ballots))
+    }
+
+    /// Move the debate to its next deliberation round
+    ///
+    /// Rounds are zero-indexed, so a debate with `max_rounds = 3` runs rounds
+    /// 0, 1 and 2. Votes from earlier rounds are kept for the record, and every
+    /// agent may cast a fresh vote in the new round that replaces its earlier
+    /// ones in the tally. The closing round's
+    /// scores are snapshotted into `round_results` first.
+    pub fn advance_round(
+        ctx: Context<AdvanceRound>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        require!(
+            debate.current_round.saturating_add(1) < debate.max_rounds,
+            ErrorCode::MaxRoundsReached
+        );
+
+        debate.record_round_result(Clock::get()?.unix_timestamp)?;
+        debate.current_round += 1;
+
+        msg!(
+            "Debate {} advanced to round {} of {}",
+            debate.debate_id,
+            debate.current_round,
+            debate.max_rounds
+        );
+
+        Ok(())
+    }
+
+    /// Reorder the recorded votes by `timestamp`, keeping arrival order among
+    /// equal timestamps. Batched and relayed votes can arrive out of order;
+    /// run this before `tally_votes` when order matters. Already-ordered
+    /// votes are left as they are.
+    pub fn sort_votes_by_time(ctx: Context<SortVotesByTime>) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            !debate.votes_tallied,
+            ErrorCode::VotesAlreadyTallied
+        );
+
+        debate.sort_votes_by_time();
+
+        msg!("Sorted {} votes by timestamp", debate.votes.len());
+        Ok(())
+    }
+
+    /// Push back the voting deadline. Extensions accumulate up to
+    /// `MAX_DEADLINE_EXTENSION` over the life of the debate.
+    pub fn extend_deadline(
+        ctx: Context<ExtendDeadline>,
+        additional_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        require!(
+            additional_seconds > 0,
+            ErrorCode::InvalidDeadlineExtension
+        );
+
+        let total_extension = debate
+            .deadline_extension
+            .checked_add(additional_seconds)
+            .filter(|total| *total <= MAX_DEADLINE_EXTENSION)
+            .ok_or(ErrorCode::DeadlineExtensionLimit)?;
+
+        debate.deadline = debate
+            .deadline
+            .checked_add(additional_seconds)
+            .ok_or(ErrorCode::InvalidDeadlineExtension)?;
+        debate.deadline_extension = total_extension;
+
+        emit!(DeadlineExtended {
+            debate_id: debate.debate_id.clone(),
+            deadline: debate.deadline,
+            total_extension,
+        });
+
+        msg!("Debate {} deadline extended to {}", debate.debate_id, debate.deadline);
+        Ok(())
+    }
+
+    /// Record an external signal (e.g. a market price) the council is weighing
+    pub fn attach_oracle_context(
+        ctx: Context<AttachOracleContext>,
+        key: String,
+        value: i64,
+    ) -> Result<()> {
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        require!(
+            key.len() <= MAX_ORACLE_KEY_LEN,
+            ErrorCode::OracleKeyTooLong
+        );
+
+        require!(
+            debate.oracle_context.len() < MAX_ORACLE_ENTRIES,
+            ErrorCode::OracleContextFull
+        );
+
+        msg!("Oracle context attached to debate {}: {} = {}", debate.debate_id, key, value);
+        debate.oracle_context.push(OracleEntry { key, value });
+        Ok(())
+    }
+
+    /// Tally votes and determine outcome
+    ///
+    /// Debates holding conditional votes must pass each `depends_on` debate
+    /// in `remaining_accounts` (any order); each must be a tallied debate of
+    /// this program with a decided outcome.
+    pub fn tally_votes(
+        ctx: Context<TallyVotes>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            !debate.governed,
+            ErrorCode::GovernanceRequired
+        );
+
+        tally_debate(debate, ctx.remaining_accounts)
+    }
+
+    /// Finalize a debate whose voting deadline has passed
+    ///
+    /// Permissionless so an unattended council never depends on a single
+    /// authority to wrap up. Debates that never reached quorum are completed untallied.
+    pub fn expire_debate(
+        ctx: Context<ExpireDebate>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Active,
+            ErrorCode::DebateNotActive
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now > debate.deadline,
+            ErrorCode::DeadlineNotReached
+        );
+
+        if debate.votes.is_empty() || !debate.meets_quorum() {
+            debate.status = DebateStatus::Completed;
+            debate.completion_timestamp = now;
+            msg!("Debate expired without reaching quorum: {}", debate.debate_id);
+        } else {
+            resolve_conditions(debate, ctx.remaining_accounts)?;
+            debate.tally(now)?;
+            msg!("Debate expired and tallied: {}", debate.debate_id);
+        }
+
+        Ok(())
+    }
+
+    /// Close a debate (emergency stop)
+    pub fn close_debate(
+        ctx: Context<CloseDebate>,
+        reason: String,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            !debate.governed,
+            ErrorCode::GovernanceRequired
+        );
+
+        require!(
+            !reason.is_empty() && reason.len() <= MAX_CLOSE_REASON_LEN,
+            ErrorCode::InvalidCloseReason
+        );
+
+        close(debate, Some(reason))
+    }
+
+    /// Name the relayers allowed to submit `cast_vote_relayed` for a debate
+    pub fn initialize_relayers(
+        ctx: Context<InitializeRelayers>,
+        relayers: Vec<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            !relayers.is_empty()
+                && relayers.len() <= MAX_RELAYERS
+                && relayers
+                    .iter()
+                    .enumerate()
+                    .all(|(i, key)| !relayers[..i].contains(key)),
+            ErrorCode::InvalidRelayers
+        );
+
+        let relayer_set = &mut ctx.accounts.relayer_set;
+        relayer_set.debate = ctx.accounts.debate.key();
+        relayer_set.relayers = relayers;
+
+        msg!(
+            "{} relayers authorized for debate {}",
+            relayer_set.relayers.len(),
+            ctx.accounts.debate.debate_id
+        );
+        Ok(())
+    }
+
+    /// Put the debate under M-of-N control: afterwards `tally_votes` and
+    /// `close_debate` only run through `approve_action` once `threshold` of
+    /// `authorities` have approved
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        authorities: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            !authorities.is_empty()
+                && authorities.len() <= MAX_GOVERNANCE_AUTHORITIES
+                && threshold >= 1
+                && threshold as usize <= authorities.len()
+                && authorities
+                    .iter()
+                    .enumerate()
+                    .all(|(i, key)| !authorities[..i].contains(key)),
+            ErrorCode::InvalidGovernance
+        );
+
+        let governance = &mut ctx.accounts.governance;
+        governance.debate = ctx.accounts.debate.key();
+        governance.authorities = authorities;
+        governance.threshold = threshold;
+        governance.pending_action = None;
+        governance.approvals = Vec::new();
+
+        ctx.accounts.debate.governed = true;
+
+        msg!(
+            "Governance initialized for debate {}: {} of {}",
+            ctx.accounts.debate.debate_id,
+            threshold,
+            governance.authorities.len()
+        );
+        Ok(())
+    }
+
+    /// Approve a privileged action, proposing it if none is pending. The
+    /// approval that reaches the threshold executes the action.
+    pub fn approve_action(
+        ctx: Context<ApproveAction>,
+        action: GovernedAction,
+    ) -> Result<()> {
+        let governance = &mut ctx.accounts.governance;
+        let approver = ctx.accounts.approver.key();
+
+        require!(
+            governance.authorities.contains(&approver),
+            ErrorCode::NotGovernanceAuthority
+        );
+
+        match governance.pending_action {
+            None => governance.pending_action = Some(action),
+            Some(pending) => require!(
+                pending == action,
+                ErrorCode::PendingActionMismatch
+            ),
+        }
+
+        require!(
+            !governance.approvals.contains(&approver),
+            ErrorCode::AlreadyApproved
+        );
+        governance.approvals.push(approver);
+
+        msg!(
+            "Action {:?} approved ({} of {})",
+            action,
+            governance.approvals.len(),
+            governance.threshold
+        );
+
+        if governance.approvals.len() < governance.threshold as usize {
+            return Ok(());
+        }
+
+        // Approvals may gather while paused, but the action waits for unpause
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        governance.pending_action = None;
+        governance.approvals.clear();
+
+        let debate = &mut ctx.accounts.debate;
+        match action {
+            GovernedAction::TallyVotes => tally_debate(debate, ctx.remaining_accounts),
+            GovernedAction::CloseDebate => close(debate, None),
+        }
+    }
+
+    /// Drop the pending action and its approvals; any governance authority may cancel
+    pub fn cancel_action(
+        ctx: Context<CancelAction>,
+    ) -> Result<()> {
+        let governance = &mut ctx.accounts.governance;
+
+        require!(
+            governance.authorities.contains(&ctx.accounts.approver.key()),
+            ErrorCode::NotGovernanceAuthority
+        );
+
+        require!(
+            governance.pending_action.is_some(),
+            ErrorCode::NoPendingAction
+        );
+
+        governance.pending_action = None;
+        governance.approvals.clear();
+
+        msg!("Pending governance action cancelled");
+        Ok(())
+    }
+
+    /// Start handing control of the debate to `new_authority`, which must
+    /// confirm with `accept_authority`
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let debate = &mut ctx.accounts.debate;
+        debate.pending_authority = Some(new_authority);
+
+        msg!("Authority transfer proposed to {}", new_authority);
+        Ok(())
+    }
+
+    /// Complete a proposed authority transfer, signed by the new authority
+    ///
+    /// The new authority must already have its `AuthorityGovernance` pause
+    /// switch, which votes on the debate require from then on.
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
+    ) -> Result<()> {
+        let debate = &mut ctx.accounts.debate;
+        let new_authority = ctx.accounts.new_authority.key();
+
+        require!(
+            debate.pending_authority == Some(new_authority),
+            ErrorCode::NotPendingAuthority
+        );
+
+        debate.authority = new_authority;
+        debate.pending_authority = None;
+
+        msg!("Authority transferred to {}", new_authority);
+        Ok(())
+    }
+
+    /// Create the pause switch for the signing authority's debates; an
+    /// authority needs one before it can open debates or take votes
+    pub fn initialize_authority_governance(
+        ctx: Context<InitializeAuthorityGovernance>,
+    ) -> Result<()> {
+        let authority_governance = &mut ctx.accounts.authority_governance;
+        authority_governance.authority = ctx.accounts.authority.key();
+        authority_governance.paused = false;
+
+        msg!("Pause switch created for authority {}", authority_governance.authority);
+        Ok(())
+    }
+
+    /// Create the index of the signing authority's debates; an authority
+    /// needs one before it can open debates
+    pub fn initialize_authority_registry(
+        ctx: Context<InitializeAuthorityRegistry>,
+    ) -> Result<()> {
+        let authority_registry = &mut ctx.accounts.authority_registry;
+        authority_registry.authority = ctx.accounts.authority.key();
+        authority_registry.total_registered = 0;
+        authority_registry.debate_ids = Vec::new();
+
+        msg!("Debate registry created for authority {}", authority_registry.authority);
+        Ok(())
+    }
+
+    /// Read a page of an authority's debate ids, oldest first
+    ///
+    /// `limit` is clamped to `MAX_DEBATE_IDS_PAGE`; an `offset` equal to the
+    /// number of retained ids returns an empty page. Only the latest
+    /// `MAX_REGISTRY_DEBATES` ids are retained (see `AuthorityRegistry`).
+    pub fn list_debates(
+        ctx: Context<ListDebates>,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<String>> {
+        let registry = &ctx.accounts.authority_registry;
+
+        let start = offset as usize;
+        require!(
+            start <= registry.debate_ids.len(),
+            ErrorCode::InvalidOffset
+        );
+
+        let end = (start + (limit as usize).min(MAX_DEBATE_IDS_PAGE)).min(registry.debate_ids.len());
+        Ok(registry.debate_ids[start..end].to_vec())
+    }
+
+    /// Stop new debates and every instruction that changes votes, phase or
+    /// outcome across every debate the authority owns
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        ctx.accounts.authority_governance.paused = true;
+
+        msg!("Voting paused for authority {}", ctx.accounts.authority.key());
+        Ok(())
+    }
+
+    /// Lift a pause set by `pause`
+    pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
+        ctx.accounts.authority_governance.paused = false;
+
+        msg!("Voting unpaused for authority {}", ctx.accounts.authority.key());
+        Ok(())
+    }
+
+    /// Flag a suspected error in a tallied outcome during the dispute window
+    pub fn dispute_outcome(
+        ctx: Context<DisputeOutcome>,
+        agent_id: String,
+        reason: String,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Completed && debate.votes_tallied,
+            ErrorCode::InvalidDebateStatus
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now <= debate.completion_timestamp.saturating_add(debate.dispute_window_seconds),
+            ErrorCode::DisputeWindowClosed
+        );
+
+        authorize_voter(debate, &agent_id, &ctx.accounts.disputer.key())?;
+
+        require!(
+            agent_id.len() <= MAX_AGENT_ID_LEN,
+            ErrorCode::AgentIdTooLong
+        );
+
+        require!(
+            reason.len() <= MAX_DISPUTE_REASON_LEN,
+            ErrorCode::DisputeReasonTooLong
+        );
+
+        require!(
+            !debate.disputes.iter().any(|d| d.agent_id == agent_id),
+            ErrorCode::AlreadyDisputed
+        );
+
+        require!(
+            debate.disputes.len() < MAX_DISPUTES,
+            ErrorCode::DisputesFull
+        );
+
+        msg!("Outcome disputed by agent: {}", agent_id);
+        debate.disputes.push(Dispute {
+            agent_id,
+            reason,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Lock a tallied outcome once its dispute window has passed
+    pub fn finalize_outcome(
+        ctx: Context<FinalizeOutcome>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Completed && debate.votes_tallied,
+            ErrorCode::InvalidDebateStatus
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now > debate.completion_timestamp.saturating_add(debate.dispute_window_seconds),
+            ErrorCode::DisputeWindowOpen
+        );
+
+        debate.status = DebateStatus::Finalized;
+
+        msg!(
+            "Outcome finalized for debate {} with {} disputes",
+            debate.debate_id,
+            debate.disputes.len()
+        );
+        Ok(())
+    }
+
+    /// Return the creation bond to the authority once the debate has completed
+    /// or been closed
+    pub fn reclaim_bond(
+        ctx: Context<ReclaimBond>,
+    ) -> Result<()> {
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            matches!(
+                debate.status,
+                DebateStatus::Completed | DebateStatus::Finalized | DebateStatus::Closed
+            ),
+            ErrorCode::InvalidDebateStatus
+        );
+
+        require!(
+            !debate.bond_reclaimed,
+            ErrorCode::BondAlreadyReclaimed
+        );
+
+        debate.bond_reclaimed = true;
+        let bond = debate.bond_lamports;
+
+        // The debate is program-owned, so its lamports can be moved directly
+        **debate.to_account_info().try_borrow_mut_lamports()? -= bond;
+        **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += bond;
+
+        msg!("Bond of {} lamports reclaimed for debate {}", bond, debate.debate_id);
+        Ok(())
+    }
+
+    /// Settle a tie left open by the `AuthorityDecides` tie-break policy
+    pub fn resolve_tie(
+        ctx: Context<ResolveTie>,
+        outcome: VoteOption,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.votes_tallied,
+            ErrorCode::VotesNotTallied
+        );
+
+        require!(
+            debate.outcome.is_none(),
+            ErrorCode::TieAlreadyResolved
+        );
+
+        // Only an option that actually shares the top score may be chosen
+        let top = [VoteOption::Support, VoteOption::Oppose, VoteOption::Neutral]
+            .iter()
+            .map(|&o| debate.outcome_score(o))
+            .max()
+            .unwrap_or(0);
+        require!(
+            outcome != VoteOption::Abstain && debate.outcome_score(outcome) == top,
+            ErrorCode::InvalidTieResolution
+        );
+
+        debate.settle_outcome(Some(outcome));
+
+        msg!(
+            "Tie resolved by authority - Outcome: {:?}, Consensus: {}, No consensus: {}",
+            debate.outcome,
+            debate.consensus_reached,
+            debate.no_consensus
+        );
+        Ok(())
+    }
+
+    /// Combine the tallied results of two related debates into an aggregate
+    ///
+    /// Both debates must use the same `ScoreScale`. Scores and counts are
+    /// summed (saturating at `u32::MAX`). The aggregate outcome is the option
+    /// with the strictly highest combined score; ties fall back to Neutral.
+    pub fn merge_debates(
+        ctx: Context<MergeDebates>,
+    ) -> Result<()> {
+        let first = &ctx.accounts.first_debate;
+        let second = &ctx.accounts.second_debate;
+
+        require!(
+            first.key() != second.key() && first.score_scale == second.score_scale,
+            ErrorCode::InvalidMerge
+        );
+
+        for debate in [first, second] {
+            require!(
+                matches!(debate.status, DebateStatus::Completed | DebateStatus::Finalized)
+                    && debate.votes_tallied,
+                ErrorCode::VotesNotTallied
+            );
+        }
+
+        let aggregate = &mut ctx.accounts.aggregate;
+        aggregate.authority = ctx.accounts.authority.key();
+        aggregate.debates = [first.key(), second.key()];
+        aggregate.support_score = first.support_score.saturating_add(second.support_score);
+        aggregate.oppose_score = first.oppose_score.saturating_add(second.oppose_score);
+        aggregate.neutral_score = first.neutral_score.saturating_add(second.neutral_score);
+        aggregate.support_count = first
+            .support_count
+            .checked_add(second.support_count)
+            .ok_or(ErrorCode::CountOverflow)?;
+        aggregate.oppose_count = first
+            .oppose_count
+            .checked_add(second.oppose_count)
+            .ok_or(ErrorCode::CountOverflow)?;
+        aggregate.neutral_count = first
+            .neutral_count
+            .checked_add(second.neutral_count)
+            .ok_or(ErrorCode::CountOverflow)?;
+        aggregate.abstain_count = first
+            .abstain_count
+            .checked_add(second.abstain_count)
+            .ok_or(ErrorCode::CountOverflow)?;
+        aggregate.total_votes = (first.votes.len() + second.votes.len()) as u16;
+        aggregate.outcome = aggregate.outcome();
+
+        msg!(
+            "Merged debates {} and {} - Support: {}, Oppose: {}, Neutral: {}, Outcome: {:?}",
+            first.debate_id,
+            second.debate_id,
+            aggregate.support_score,
+            aggregate.oppose_score,
+            aggregate.neutral_score,
+            aggregate.outcome
+        );
+        Ok(())
+    }
+
+    /// Copy a completed debate's results into an immutable `DebateArchive`
+    ///
+    /// With `close_original` the debate account is closed afterwards and its
+    /// rent, along with any unreclaimed bond, returned to the authority.
+    pub fn archive_debate(
+        ctx: Context<ArchiveDebate>,
+        close_original: bool,
+    ) -> Result<()> {
+        let debate = &ctx.accounts.debate;
+
+        require!(
+            matches!(debate.status, DebateStatus::Completed | DebateStatus::Finalized)
+                && debate.votes_tallied,
+            ErrorCode::VotesNotTallied
+        );
+
+        let outcome = debate.outcome.ok_or(ErrorCode::OutcomeNotDetermined)?;
+
+        let archive = &mut ctx.accounts.archive;
+        archive.debate = debate.key();
+        archive.debate_id = debate.debate_id.clone();
+        archive.authority = debate.authority;
+        archive.topic_hash = debate.topic_hash;
+        archive.outcome = outcome;
+        archive.support_score = debate.support_score;
+        archive.oppose_score = debate.oppose_score;
+        archive.neutral_score = debate.neutral_score;
+        archive.score_scale = debate.score_scale;
+        archive.total_votes = debate.votes.len() as u16;
+        archive.support_count = debate.support_count;
+        archive.oppose_count = debate.oppose_count;
+        archive.neutral_count = debate.neutral_count;
+        archive.abstain_count = debate.abstain_count;
+        archive.timestamp = debate.timestamp;
+        archive.deadline = debate.deadline;
+        archive.completion_timestamp = debate.completion_timestamp;
+        archive.archived_at = Clock::get()?.unix_timestamp;
+
+        msg!("Debate {} archived - Outcome: {:?}", archive.debate_id, archive.outcome);
+
+        if close_original {
+            ctx.accounts.debate.close(ctx.accounts.authority.to_account_info())?;
+            msg!("Original debate account closed");
+        }
+        Ok(())
+    }
+
+    /// Reopen a debate that was closed by mistake
+    pub fn reopen_debate(
+        ctx: Context<ReopenDebate>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            debate.status == DebateStatus::Closed,
+            ErrorCode::InvalidDebateStatus
+        );
+
+        require!(
+            !debate.votes_tallied,
+            ErrorCode::VotesAlreadyTallied
+        );
+
+        debate.status = DebateStatus::Active;
+        debate.close_reason = None;
+
+        msg!("Debate reopened: {}", debate.debate_id);
+        Ok(())
+    }
+
+    /// Get vote results
+    pub fn get_results(
+        ctx: Context<GetResults>,
+    ) -> Result<VoteResults> {
+        let debate = &ctx.accounts.debate;
+
+        require!(
+            debate.votes_tallied,
+            ErrorCode::VotesNotTallied
+        );
+
+        debate.results()
+    }
+
+    /// Compute the current standing without tallying
+    ///
+    /// Scores the votes cast so far exactly as `tally_votes` would, but on a
+    /// copy: the debate is not modified and quorum is not enforced. The
+    /// outcome is None while an `AuthorityDecides` debate is tied.
+    pub fn preview_tally(
+        ctx: Context<PreviewTally>,
+    ) -> Result<VoteResults> {
+        let mut preview = ctx.accounts.debate.clone().into_inner();
+        preview.score(Clock::get()?.unix_timestamp)?;
+        preview.results()
+    }
+
+    /// Read a page of individual votes
+    ///
+    /// `limit` is clamped to `MAX_VOTES_PAGE`; an `offset` equal to the vote
+    /// count returns an empty page.
+    pub fn get_votes(
+        ctx: Context<GetVotes>,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<Vote>> {
+        let debate = &ctx.accounts.debate;
+
+        let start = offset as usize;
+        require!(
+            start <= debate.votes.len(),
+            ErrorCode::InvalidOffset
+        );
+
+        let end = (start + (limit as usize).min(MAX_VOTES_PAGE)).min(debate.votes.len());
+        Ok(debate.votes[start..end].to_vec())
+    }
+
+    /// Create the flag counter for an agent, shared across debates
+    pub fn initialize_agent_flags(
+        ctx: Context<InitializeAgentFlags>,
+        agent_id: String,
+    ) -> Result<()> {
+        require!(
+            agent_id.len() <= MAX_AGENT_ID_LEN,
+            ErrorCode::AgentIdTooLong
+        );
+
+        let agent_flags = &mut ctx.accounts.agent_flags;
+        agent_flags.agent_id = agent_id;
+        agent_flags.flags = 0;
+        agent_flags.last_evidence = [0; 32];
+        agent_flags.last_debate = Pubkey::default();
+
+        msg!("Flag record created for agent: {}", agent_flags.agent_id);
+        Ok(())
+    }
+
+    /// Flag an agent's latest vote as inconsistent with its reasoning
+    ///
+    /// Adjudicated off-chain; `evidence_hash` commits to the evidence. The
+    /// flagged vote is kept but excluded from scoring, participation and quorum.
+    pub fn flag_inconsistent_vote(
+        ctx: Context<FlagInconsistentVote>,
+        agent_id: String,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.authority_governance.paused,
+            ErrorCode::GovernancePaused
+        );
+
+        let debate_key = ctx.accounts.debate.key();
+        let debate = &mut ctx.accounts.debate;
+
+        require!(
+            !debate.votes_tallied,
+            ErrorCode::VotesAlreadyTallied
+        );
+
+        let vote = debate
+            .votes
+            .iter_mut()
+            .rev()
+            .find(|v| v.agent_id == agent_id && v.question.is_none())
+            .ok_or(ErrorCode::VoteNotFound)?;
+
+        require!(
+            !vote.flagged,
+            ErrorCode::VoteAlreadyFlagged
+        );
+
+        vote.flagged = true;
+
+        let agent_flags = &mut ctx.accounts.agent_flags;
+        agent_flags.flags = agent_flags.flags.saturating_add(1);
+        agent_flags.last_evidence = evidence_hash;
+        agent_flags.last_debate = debate_key;
+
+        msg!("Vote flagged for agent: {}, total flags: {}", agent_id, agent_flags.flags);
+        Ok(())
+    }
+
+    /// Read one agent's most recent vote
+    pub fn get_agent_vote(
+        ctx: Context<GetAgentVote>,
+        agent_id: String,
+    ) -> Result<Vote> {
+        let debate = &ctx.accounts.debate;
+
+        // Votes are appended in order, so the last match is the latest round
+        let vote = debate
+            .votes
+            .iter()
+            .rev()
+            .find(|v| v.agent_id == agent_id && v.question.is_none())
+            .ok_or(ErrorCode::VoteNotFound)?;
+
+        Ok(vote.clone())
+    }
+
+    /// Read each agent's latest main-question vote and whether it matched the
+    /// tallied outcome; agents whose latest vote is custom are left out
+    pub fn get_agent_outcomes(
+        ctx: Context<GetAgentOutcomes>,
+    ) -> Result<Vec<AgentOutcome>> {
+        let debate = &ctx.accounts.debate;
+
+        require!(
+            debate.votes_tallied,
+            ErrorCode::VotesNotTallied
+        );
+
+        let outcome = debate.outcome.ok_or(ErrorCode::OutcomeNotDetermined)?;
+
+        // Superseded votes drop out, so each agent is reported at most once
+        let outcomes = debate
+            .votes
+            .iter()
+            .filter(|v| v.on_main_question() && !debate.superseded(v))
+            .map(|vote| AgentOutcome {
+                agent_id: vote.agent_id.clone(),
+                vote_option: vote.vote_option,
+                confidence: vote.confidence,
+                on_winning_side: vote.vote_option == outcome,
+            })
+            .collect();
+
+        Ok(outcomes)
+    }
+
+    /// Number of votes the debate can still accept before it is full
+    pub fn remaining_vote_slots(
+        ctx: Context<RemainingVoteSlots>,
+    ) -> Result<u16> {
+        Ok(MAX_VOTES.saturating_sub(ctx.accounts.debate.votes.len()) as u16)
+    }
+
+    /// Unused bytes in the debate account, measured against its current
+    /// serialized size. The account is sized for `MAX_VOTES` full-length
+    /// votes, so `remaining_vote_slots` is what limits new votes.
+    pub fn bytes_remaining(
+        ctx: Context<BytesRemaining>,
+    ) -> Result<u32> {
+        let debate = &ctx.accounts.debate;
+        let used = 8 + debate.try_to_vec()?.len();
+        Ok(debate.to_account_info().data_len().saturating_sub(used) as u32)
+    }
+
+    /// Read the per-round score snapshots, oldest round first
+    pub fn get_round_results(
+        ctx: Context<GetRoundResults>,
+    ) -> Result<Vec<RoundResult>> {
+        Ok(ctx.accounts.debate.round_results.clone())
+    }
+
+    /// Outcome and scores of each sub-question, parallel to `sub_questions`
+    pub fn get_question_results(
+        ctx: Context<GetQuestionResults>,
+    ) -> Result<Vec<QuestionResult>> {
+        let debate = &ctx.accounts.debate;
+
+        require!(
+            debate.votes_tallied,
+            ErrorCode::VotesNotTallied
+        );
+
+        Ok(debate.question_results.clone())
+    }
+
+    /// Read a debate's metadata without its ballots
+    pub fn get_debate_summary(
+        ctx: Context<GetDebateSummary>,
+    ) -> Result<DebateSummary> {
+        let debate = &ctx.accounts.debate;
+
+        Ok(DebateSummary {
+            debate_id: debate.debate_id.clone(),
+            topic: debate.topic.clone(),
+            topic_hash: debate.topic_hash,
+            status: debate.status.clone(),
+            current_round: debate.current_round,
+            max_rounds: debate.max_rounds,
+            vote_count: debate.votes.len() as u16,
+            votes_tallied: debate.votes_tallied,
+            close_reason: debate.close_reason.clone(),
+            unrevealed_commits: debate.unrevealed_commits,
+        })
+    }
+
+    /// Report whether a debate has been initialized under `debate_id`
+    pub fn ensure_debate(
+        ctx: Context<EnsureDebate>,
+        _debate_id: String,
+    ) -> Result<bool> {
+        Ok(debate_exists(&ctx.accounts.debate))
+    }
+
+    /// Fail with `DebateAlreadyExists` if `debate_id` is taken; placed ahead
+    /// of `initialize_debate` in a transaction to check-then-create
+    pub fn assert_debate_absent(
+        ctx: Context<EnsureDebate>,
+        debate_id: String,
+    ) -> Result<()> {
+        require!(
+            !debate_exists(&ctx.accounts.debate),
+            ErrorCode::DebateAlreadyExists
+        );
+
+        msg!("Debate id available: {}", debate_id);
+        Ok(())
+    }
+
+}
+
+/// Whether the debate PDA holds an initialized account owned by this program
+fn debate_exists(debate: &AccountInfo) -> bool {
+    debate.owner == &crate::ID && !debate.data_is_empty()
+}
+
+/// Check `voter` may sign for `agent_id` under the debate's registry
+fn authorize_voter(debate: &Debate, agent_id: &str, voter: &Pubkey) -> Result<()> {
+    require!(
+        debate.is_allowed(voter),
+        ErrorCode::UnauthorizedVoter
+    );
+
+    require!(
+        debate.agent_key_matches(agent_id, voter),
+        ErrorCode::AgentKeyMismatch
+    );
+    Ok(())
+}
+
+/// Stake-weighted debates read the voter's token balance for the stake mint;
+/// other debates weight every voter as 1
+fn stake_weight_of(
+    debate: &Debate,
+    voter: &Pubkey,
+    voter_stake: &Option<Account<TokenAccount>>,
+) -> Result<u64> {
+    match debate.stake_mint {
+        Some(mint) => {
+            let stake = voter_stake
+                .as_ref()
+                .ok_or(ErrorCode::StakeAccountRequired)?;
+            require_keys_eq!(
+                stake.key(),
+                get_associated_token_address(voter, &mint),
+                ErrorCode::InvalidStakeAccount
+            );
+            Ok(stake.amount)
+        }
+        None => Ok(1),
+    }
+}
+
+/// Address of `agent_id`'s council_selection `AgentProfile`
+fn agent_profile_address(agent_id: &str) -> Pubkey {
+    Pubkey::find_program_address(&[AgentProfile::SEED, agent_id.as_bytes()], &council_selection::ID).0
+}
+
+/// Reputation `profile` gives a vote cast by `voter`, capped at
+/// `MAX_REPUTATION`; the profile must be keyed to `voter`
+fn profile_reputation(profile: &AgentProfile, voter: &Pubkey) -> Result<u16> {
+    require_keys_eq!(
+        profile.pubkey,
+        *voter,
+        ErrorCode::AgentKeyMismatch
+    );
+    Ok(profile.reputation.min(MAX_REPUTATION))
+}
+
+/// Give the vote `record_vote` just pushed the reputation from the agent's
+/// council_selection `profile`, whose address the caller has checked. Every
+/// voting agent must be registered with council_selection.
+fn apply_agent_profile(debate: &mut Debate, profile: &AccountInfo, voter: &Pubkey) -> Result<()> {
+    require!(
+        profile.owner == &council_selection::ID,
+        ErrorCode::InvalidAgentProfile
+    );
+    let profile = AgentProfile::try_deserialize(&mut &profile.try_borrow_data()?[..])
+        .map_err(|_| ErrorCode::InvalidAgentProfile)?;
+    let reputation = profile_reputation(&profile, voter)?;
+    if let Some(vote) = debate.votes.last_mut() {
+        vote.reputation = reputation;
+    }
+    Ok(())
+}
+
+/// Validate and append a vote submitted by `voter`. Shared by direct, revealed
+/// and relayed votes; callers check status, phase, deadline and who may submit.
+fn record_vote(debate: &mut Debate, voter: Pubkey, stake_weight: u64, input: VoteInput) -> Result<()> {
+    record_vote_on(debate, voter, stake_weight, None, input)
+}
+
+/// Record a vote on the main question (`None`) or one of `sub_questions`;
+/// an agent gets one vote per question per round
+fn record_vote_on(
+    debate: &mut Debate,
+    voter: Pubkey,
+    stake_weight: u64,
+    question: Option<u8>,
+    input: VoteInput,
+) -> Result<()> {
+    let VoteInput {
+        agent_id,
+        vote_option,
+        confidence,
+        reasoning,
+    } = input;
+
+    require!(
+        confidence <= 100,
+        ErrorCode::InvalidConfidence
+    );
+
+    require!(
+        confidence >= debate.min_confidence,
+        ErrorCode::ConfidenceTooLow
+    );
+
+    require!(
+        agent_id.len() <= MAX_AGENT_ID_LEN,
+        ErrorCode::AgentIdTooLong
+    );
+
+    let (reasoning, reasoning_truncated) = debate.fit_reasoning(reasoning)?;
+
+    require!(
+        debate.votes.len() < MAX_VOTES,
+        ErrorCode::DebateFull
+    );
+
+    // Check if agent already voted on this question in the current round
+    let current_round = debate.current_round;
+    let existing_vote = debate
+        .votes
+        .iter()
+        .find(|v| v.agent_id == agent_id && v.round == current_round && v.question == question);
+    require!(existing_vote.is_none(), ErrorCode::AlreadyVoted);
+
+    require!(
+        !debate.delegations.iter().any(|d| d.from_agent == agent_id),
+        ErrorCode::AlreadyDelegated
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Sub-question votes are rate limited only by their one-per-round rule
+    if question.is_none() {
+        debate.rate_limit(&agent_id, now)?;
+    }
+
+    debate.spend_confidence(&agent_id, 0, confidence)?;
+
+    let vote = Vote {
+        agent_id: agent_id.clone(),
+        vote_option,
+        confidence,
+        reasoning,
+        timestamp: now,
+        voter,
+        last_updated: now,
+        round: current_round,
+        stake_weight,
+        reputation: debate.reputation_of(&agent_id),
+        reasoning_truncated,
+        reasoning_is_hash: false,
+        condition: None,
+        flagged: false,
+        applied_weight: 0,
+        custom_option: None,
+        question,
+    };
+
+    debate.votes.push(vote);
+    debate.last_vote_timestamp = now;
+
+    emit!(VoteCast {
+        debate_id: debate.debate_id.clone(),
+        agent_id: agent_id.clone(),
+        vote_option,
+        confidence,
+        round: current_round,
+    });
+
+    msg!(
+        "Vote cast by agent: {}, option: {:?}, confidence: {}",
+        agent_id,
+        vote_option,
+        confidence
+    );
+
+    Ok(())
+}
+
+/// Tally a debate that is open for tallying; shared by `tally_votes` and governance
+fn tally_debate(debate: &mut Debate, dependencies: &[AccountInfo]) -> Result<()> {
+    // Commit-reveal debates tally once reveals are in; unrevealed commits are not counted
+    require!(
+        debate.status == DebateStatus::Active || debate.status == DebateStatus::Revealing,
+        ErrorCode::DebateNotActive
+    );
+
+    require!(
+        !debate.votes.is_empty(),
+        ErrorCode::NoVotes
+    );
+
+    resolve_conditions(debate, dependencies)?;
+
+    if debate.unrevealed_commits > 0 {
+        msg!(
+            "UnrevealedVote: {} commitments were never revealed and are excluded",
+            debate.unrevealed_commits
+        );
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    debate.tally(now)
+}
+
+/// Settle conditional votes against the debates they depend on, found among
+/// `dependencies`: a met condition counts as Support, an unmet one as Abstain
+fn resolve_conditions(debate: &mut Debate, dependencies: &[AccountInfo]) -> Result<()> {
+    if debate.votes.iter().all(|v| v.condition.is_none()) {
+        return Ok(());
+    }
+
+    let mut met = Vec::with_capacity(debate.vote_conditions.len());
+    for condition in &debate.vote_conditions {
+        let account = dependencies
+            .iter()
+            .find(|a| a.key() == condition.depends_on && a.owner == &crate::ID)
+            .ok_or(ErrorCode::DependencyUnresolved)?;
+        let dependency = Debate::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        require!(
+            matches!(dependency.status, DebateStatus::Completed | DebateStatus::Finalized)
+                && dependency.votes_tallied,
+            ErrorCode::DependencyUnresolved
+        );
+        let outcome = dependency.outcome.ok_or(ErrorCode::DependencyUnresolved)?;
+        met.push(outcome == condition.required_outcome);
+    }
+
+    for vote in debate.votes.iter_mut() {
+        if let Some(index) = vote.condition.take() {
+            vote.vote_option = if met[index as usize] {
+                VoteOption::Support
+            } else {
+                VoteOption::Abstain
+            };
+        }
+    }
+    Ok(())
+}
+
+/// Close a debate; shared by `close_debate` and governance, whose
+/// approved closes carry no reason
+fn close(debate: &mut Debate, reason: Option<String>) -> Result<()> {
+    debate.status = DebateStatus::Closed;
+    debate.close_reason = reason;
+
+    emit!(DebateClosed {
+        debate_id: debate.debate_id.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Debate closed: {}", debate.debate_id);
+    Ok(())
+}
+
+/// Message an agent signs for `cast_vote_relayed`; binding the debate and
+/// round keeps a signature from being replayed elsewhere, and the agent's
+/// `relay_nonce` keeps it from being replayed in the same debate
+pub fn relayed_vote_message(
+    debate: &Pubkey,
+    agent_id: &str,
+    vote_option: VoteOption,
+    confidence: u8,
+    reasoning: &str,
+    round: u8,
+    nonce: u32,
+) -> [u8; 32] {
+    hashv(&[
+        debate.as_ref(),
+        agent_id.as_bytes(),
+        &[vote_option as u8],
+        &[confidence],
+        reasoning.as_bytes(),
+        &[round],
+        &nonce.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Whether `ix` is an Ed25519 program instruction checking exactly one
+/// signature by `pubkey` over `message`, with all data inline
+fn ed25519_verifies(ix: &Instruction, pubkey: &Pubkey, signature: &[u8; 64], message: &[u8]) -> bool {
+    // Layout: count (u8), padding (u8), then seven u16 offsets per signature
+    const HEADER_LEN: usize = 2 + 14;
+    let data = &ix.data;
+    if ix.program_id != ed25519_program::ID || data.len() < HEADER_LEN || data[0] != 1 {
+        return false;
+    }
+    let read = |at: usize| u16::from_le_bytes([data[2 + at * 2], data[3 + at * 2]]) as usize;
+    let (signature_offset, public_key_offset, message_offset, message_len) =
+        (read(0), read(2), read(4), read(5));
+    let inline = [read(1), read(3), read(6)].iter().all(|&index| index == u16::MAX as usize);
+
+    inline
+        && data.get(signature_offset..signature_offset + 64) == Some(&signature[..])
+        && data.get(public_key_offset..public_key_offset + 32) == Some(pubkey.as_ref())
+        && data.get(message_offset..message_offset + message_len) == Some(message)
+}
+
+/// Read a debate's ranked ballots from `accounts`, which must hold each of
+/// its `count` ballot accounts exactly once
+fn load_ranked_ballots(debate: &Pubkey, count: u16, accounts: &[AccountInfo]) -> Result<Vec<RankedBallot>> {
+    require!(
+        accounts.len() == count as usize,
+        ErrorCode::InvalidRankedBallots
+    );
+
+    let mut ballots = Vec::with_capacity(accounts.len());
+    for (i, account) in accounts.iter().enumerate() {
+        require!(
+            account.owner == &crate::ID && !accounts[..i].iter().any(|a| a.key() == account.key()),
+            ErrorCode::InvalidRankedBallots
+        );
+        let ballot = RankedBallot::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        require!(
+            ballot.debate == *debate,
+            ErrorCode::InvalidRankedBallots
+        );
+        ballots.push(ballot);
+    }
+    Ok(ballots)
+}
+
+/// Option that wins a strict majority of the pairwise contests against every
+/// other option, or None when no option does
+fn condorcet_winner(option_count: usize, ballots: &[RankedBallot]) -> Option<u8> {
+    // preferences[a][b]: ballots preferring option a over option b
+    let mut preferences = [[0u16; MAX_RANKED_OPTIONS]; MAX_RANKED_OPTIONS];
+    for ballot in ballots {
+        for (position, &preferred) in ballot.rankings.iter().enumerate() {
+            let row = &mut preferences[preferred as usize];
+            for (other, count) in row.iter_mut().enumerate().take(option_count) {
+                let ranked_below = ballot.rankings[..position].iter().all(|&o| o as usize != other);
+                if other != preferred as usize && ranked_below {
+                    *count += 1;
+                }
+            }
+        }
+    }
+
+    (0..option_count)
+        .find(|&a| (0..option_count).all(|b| a == b || preferences[a][b] > preferences[b][a]))
+        .map(|a| a as u8)
+}
+
+/// Hash a commit-reveal vote commits to
+pub fn vote_commitment(vote_option: VoteOption, confidence: u8, nonce: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[vote_option as u8], &[confidence], nonce]).to_bytes()
+}
+
+
+#[derive(Accounts)]
+#[instruction(debate_id: String)]
+pub struct InitializeDebate<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Debate::INIT_SPACE,
+        seeds = [b"debate", debate_id.as_bytes()],
+        bump
+    )]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    #[account(
+        mut,
+        seeds = [AuthorityRegistry::SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub authority_registry: Account<'info, AuthorityRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent_id: String)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub voter: Signer<'info>,
+
+    /// Voter's associated token account, required when the debate has a stake mint
+    pub voter_stake: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: the agent's council_selection profile, read by `apply_agent_profile`
+    #[account(
+        seeds = [AgentProfile::SEED, agent_id.as_bytes()],
+        bump,
+        seeds::program = council_selection::ID
+    )]
+    pub agent_profile: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent_id: String)]
+pub struct CastVoteRelayed<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    #[account(seeds = [b"relayers", debate.key().as_ref()], bump)]
+    pub relayer_set: Account<'info, RelayerSet>,
+
+    pub relayer: Signer<'info>,
+
+    /// Agent's associated token account, required when the debate has a stake mint
+    pub voter_stake: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: the agent's council_selection profile, read by `apply_agent_profile`
+    #[account(
+        seeds = [AgentProfile::SEED, agent_id.as_bytes()],
+        bump,
+        seeds::program = council_selection::ID
+    )]
+    pub agent_profile: UncheckedAccount<'info>,
+
+    /// CHECK: the instructions sysvar, checked by address
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRelayers<'info> {
+    #[account(has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RelayerSet::INIT_SPACE,
+        seeds = [b"relayers", debate.key().as_ref()],
+        bump
+    )]
+    pub relayer_set: Account<'info, RelayerSet>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BatchCastVotes<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent_id: String)]
+pub struct CommitVote<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteCommitment::INIT_SPACE,
+        seeds = [b"commitment", debate.key().as_ref(), agent_id.as_bytes()],
+        bump
+    )]
+    pub commitment: Account<'info, VoteCommitment>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BeginReveal<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent_id: String)]
+pub struct RevealVote<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    #[account(
+        mut,
+        seeds = [b"commitment", debate.key().as_ref(), agent_id.as_bytes()],
+        bump,
+        has_one = debate,
+        has_one = voter,
+        close = voter
+    )]
+    pub commitment: Account<'info, VoteCommitment>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// Voter's associated token account, required when the debate has a stake mint
+    pub voter_stake: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: the agent's council_selection profile, read by `apply_agent_profile`
+    #[account(
+        seeds = [AgentProfile::SEED, agent_id.as_bytes()],
+        bump,
+        seeds::program = council_selection::ID
+    )]
+    pub agent_profile: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepUnrevealed<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    #[account(
+        mut,
+        has_one = debate,
+        has_one = voter,
+        close = voter
+    )]
+    pub commitment: Account<'info, VoteCommitment>,
+
+    /// CHECK: receives the commitment's rent; matched by `has_one`
+    #[account(mut)]
+    pub voter: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshVote<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub voter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RetractVote<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub voter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeVote<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub voter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateVote<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub delegator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent_id: String)]
+pub struct CastRankedVote<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + RankedBallot::INIT_SPACE,
+        seeds = [b"ranked_ballot", debate.key().as_ref(), agent_id.as_bytes()],
+        bump
+    )]
+    pub ballot: Account<'info, RankedBallot>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TallyRanked<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FindCondorcetWinner<'info> {
+    pub debate: Account<'info, Debate>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceRound<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SortVotesByTime<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendDeadline<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AttachOracleContext<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TallyVotes<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireDebate<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDebate<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Governance::INIT_SPACE,
+        seeds = [b"governance", debate.key().as_ref()],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveAction<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    #[account(
+        mut,
+        seeds = [b"governance", debate.key().as_ref()],
+        bump,
+        has_one = debate
+    )]
+    pub governance: Account<'info, Governance>,
+
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAction<'info> {
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        mut,
+        seeds = [b"governance", debate.key().as_ref()],
+        bump,
+        has_one = debate
+    )]
+    pub governance: Account<'info, Governance>,
+
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeOutcome<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub disputer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeOutcome<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent_id: String)]
+pub struct InitializeAgentFlags<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AgentFlags::INIT_SPACE,
+        seeds = [b"agent_flags", agent_id.as_bytes()],
+        bump
+    )]
+    pub agent_flags: Account<'info, AgentFlags>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent_id: String)]
+pub struct FlagInconsistentVote<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    #[account(mut, seeds = [b"agent_flags", agent_id.as_bytes()], bump)]
+    pub agent_flags: Account<'info, AgentFlags>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimBond<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MergeDebates<'info> {
+    #[account(has_one = authority)]
+    pub first_debate: Account<'info, Debate>,
+
+    #[account(has_one = authority)]
+    pub second_debate: Account<'info, Debate>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DebateAggregate::INIT_SPACE,
+        seeds = [b"aggregate", first_debate.key().as_ref(), second_debate.key().as_ref()],
+        bump
+    )]
+    pub aggregate: Account<'info, DebateAggregate>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ArchiveDebate<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DebateArchive::INIT_SPACE,
+        seeds = [b"archive", debate.key().as_ref()],
+        bump
+    )]
+    pub archive: Account<'info, DebateArchive>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveTie<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReopenDebate<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, debate.authority.as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetResults<'info> {
+    pub debate: Account<'info, Debate>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewTally<'info> {
+    pub debate: Account<'info, Debate>,
+}
+
+#[derive(Accounts)]
+pub struct GetVotes<'info> {
+    pub debate: Account<'info, Debate>,
+}
+
+#[derive(Accounts)]
+pub struct GetAgentVote<'info> {
+    pub debate: Account<'info, Debate>,
+}
+
+#[derive(Accounts)]
+pub struct GetAgentOutcomes<'info> {
+    pub debate: Account<'info, Debate>,
+}
+
+#[derive(Accounts)]
+pub struct RemainingVoteSlots<'info> {
+    pub debate: Account<'info, Debate>,
+}
+
+#[derive(Accounts)]
+pub struct BytesRemaining<'info> {
+    pub debate: Account<'info, Debate>,
+}
+
+#[derive(Accounts)]
+pub struct GetRoundResults<'info> {
+    pub debate: Account<'info, Debate>,
+}
+
+#[derive(Accounts)]
+pub struct GetQuestionResults<'info> {
+    pub debate: Account<'info, Debate>,
+}
+
+#[derive(Accounts)]
+pub struct GetDebateSummary<'info> {
+    pub debate: Account<'info, Debate>,
+}
+
+#[derive(Accounts)]
+#[instruction(debate_id: String)]
+pub struct EnsureDebate<'info> {
+    /// CHECK: may not exist yet; only its owner and data length are read
+    #[account(seeds = [b"debate", debate_id.as_bytes()], bump)]
+    pub debate: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(mut, has_one = authority)]
+    pub debate: Account<'info, Debate>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut)]
+    pub debate: Account<'info, Debate>,
+
+    #[account(
+        seeds = [AuthorityGovernance::SEED, new_authority.key().as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeAuthorityGovernance<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuthorityGovernance::INIT_SPACE,
+        seeds = [AuthorityGovernance::SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeAuthorityRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuthorityRegistry::INIT_SPACE,
+        seeds = [AuthorityRegistry::SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub authority_registry: Account<'info, AuthorityRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ListDebates<'info> {
+    pub authority_registry: Account<'info, AuthorityRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(
+        mut,
+        seeds = [AuthorityGovernance::SEED, authority.key().as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    #[account(
+        mut,
+        seeds = [AuthorityGovernance::SEED, authority.key().as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub authority_governance: Account<'info, AuthorityGovernance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[account]
+pub struct Debate {
+    pub debate_id: String,            // 32 bytes (max)
+    pub topic: String,                 // 128 bytes (max)
+    pub authority: Pubkey,             // 32 bytes
+    pub max_rounds: u8,                // 1 byte
+    pub current_round: u8,             // 1 byte
+    pub votes: Vec<Vote>,              // Dynamic (MAX_VOTES * Vote::SPACE)
+    pub timestamp: i64,                // 8 bytes
+    pub deadline: i64,                 // 8 bytes
+    pub completion_timestamp: i64,     // 8 bytes
+    pub status: DebateStatus,          // 1 byte
+    pub outcome: Option<VoteOption>,   // 2 bytes
+    pub support_score: u32,            // 4 bytes
+    pub oppose_score: u32,             // 4 bytes
+    pub neutral_score: u32,            // 4 bytes
+    pub votes_tallied: bool,           // 1 byte
+    pub tally_mode: TallyMode,         // 1 byte
+    pub ranked_options: Vec<String>,   // Dynamic (max 8 options * 32 bytes = 256 bytes)
+    pub ranked_ballot_count: u16,      // 2 bytes (ballots live in RankedBallot PDAs)
+    pub elimination_order: Vec<u8>,    // Dynamic (max 8 bytes)
+    pub ranked_winner: Option<u8>,     // 2 bytes
+    pub quorum: u8,                    // 1 byte
+    pub stake_mint: Option<Pubkey>,    // 33 bytes
+    pub support_count: u16,            // 2 bytes
+    pub oppose_count: u16,             // 2 bytes
+    pub neutral_count: u16,            // 2 bytes
+    pub abstain_count: u16,            // 2 bytes
+    pub delegations: Vec<Delegation>,  // Dynamic (max 10 * ~104 bytes = 1040 bytes)
+    pub tie_break: TieBreak,           // 1 byte
+    pub contested_threshold: u32,      // 4 bytes
+    pub margin: u32,                   // 4 bytes
+    pub contested: bool,               // 1 byte
+    pub min_confidence: u8,            // 1 byte
+    pub registered_agents: Vec<AgentRegistration>, // Dynamic (MAX_REGISTERED_AGENTS * AgentRegistration::SPACE)
+    pub commit_reveal: bool,           // 1 byte
+    pub abstain_weight: u32,           // 4 bytes
+    pub deadline_extension: i64,       // 8 bytes
+    pub winning_score: u32,            // 4 bytes
+    pub runner_up_score: u32,          // 4 bytes
+    pub topic_hash: [u8; 32],          // 32 bytes (all zeros when unused)
+    pub truncate_reasoning: bool,      // 1 byte
+    pub neutral_discount: u8,          // 1 byte (0-100)
+    pub oracle_context: Vec<OracleEntry>, // Dynamic (MAX_ORACLE_ENTRIES * OracleEntry::SPACE)
+    pub last_vote_timestamp: i64,      // 8 bytes
+    pub tally_cooldown_seconds: i64,   // 8 bytes
+    pub governed: bool,                // 1 byte
+    pub pending_authority: Option<Pubkey>, // 33 bytes
+    pub confidence_buckets: [u16; 4],  // 8 bytes
+    pub dispute_window_seconds: i64,   // 8 bytes
+    pub disputes: Vec<Dispute>,        // Dynamic (MAX_DISPUTES * Dispute::SPACE)
+    pub threshold_bps: u16,            // 2 bytes (0 = plurality)
+    pub consensus_reached: bool,       // 1 byte
+    pub max_weight_bps: u16,           // 2 bytes (0 = uncapped)
+    pub recency_weighting: bool,       // 1 byte
+    pub round_multipliers: Vec<u16>,   // Dynamic (MAX_ROUND_MULTIPLIERS * 2)
+    pub close_reason: Option<String>,  // 1 + 4 + MAX_CLOSE_REASON_LEN bytes
+    pub avg_confidence: u8,            // 1 byte
+    pub outcome_action: Option<String>, // 1 + 4 + MAX_OUTCOME_ACTION_LEN bytes
+    pub bond_lamports: u64,            // 8 bytes
+    pub bond_reclaimed: bool,          // 1 byte
+    pub vote_conditions: Vec<VoteCondition>, // Dynamic (MAX_VOTE_CONDITIONS * VoteCondition::SPACE)
+    pub minority_report: Option<MinorityReport>, // 7 bytes
+    pub score_scale: ScoreScale,       // 1 byte
+    pub round_results: Vec<RoundResult>, // Dynamic (MAX_ROUNDS * RoundResult::SPACE)
+    pub unrevealed_commits: u16,       // 2 bytes
+    pub abstain_counts_for_quorum: bool, // 1 byte
+    pub confidence_budget: u16,        // 2 bytes (0 = unlimited)
+    pub median_confidence: u8,         // 1 byte
+    pub reveal_deposit_lamports: u64,  // 8 bytes
+    pub reveal_window_seconds: i64,    // 8 bytes (0 = no reveal deadline)
+    pub reveal_deadline: i64,          // 8 bytes (set by `begin_reveal`)
+    pub forfeited_lamports: u64,       // 8 bytes
+    pub custom_options: Vec<String>,   // Dynamic (MAX_CUSTOM_OPTIONS * (4 + 32))
+    pub custom_scores: Vec<u32>,       // Dynamic (MAX_CUSTOM_OPTIONS * 4, parallel to custom_options)
+    pub consensus_entropy: u16,        // 2 bytes (0 = unanimous, u16::MAX = even split)
+    pub normalized: bool,              // 1 byte
+    pub result_mode: ResultMode,       // 1 byte
+    pub allocations: Vec<(VoteOption, u16)>, // Dynamic (3 * 3, `Proportional` only)
+    pub sub_questions: Vec<String>,    // Dynamic (MAX_SUB_QUESTIONS * (4 + MAX_SUB_QUESTION_LEN))
+    pub question_results: Vec<QuestionResult>, // Dynamic (MAX_SUB_QUESTIONS * QuestionResult::SPACE)
+    pub min_vote_interval_seconds: i64, // 8 bytes (0 = no rate limit)
+    pub vote_ttl_seconds: i64,         // 8 bytes (0 = votes never decay)
+    pub no_consensus: bool,            // 1 byte (Support/Oppose led but missed `threshold_bps`)
+}
+
+// Accounts created through CPI, as `init` does, are limited to 10 KiB
+const _: () = assert!(8 + Debate::INIT_SPACE <= 10_240);
+
+impl Debate {
+    pub const INIT_SPACE: usize = 32 + 128 + 32 + 1 + 1 + (4 + MAX_VOTES * Vote::SPACE) + 8 + 8 + 8 + 1 + 2 + 4 + 4 + 4 + 1 + 1
+        + (4 + 8 * (4 + 32)) + 2 + (4 + 8) + 2 + 1 + 33 + 2 + 2 + 2 + 2
+        + (4 + 1040) + 1 + 4 + 4 + 1 + 1
+        + (4 + MAX_REGISTERED_AGENTS * AgentRegistration::SPACE) + 1 + 4 + 8 + 4 + 4 + 32 + 1 + 1
+        + (4 + MAX_ORACLE_ENTRIES * OracleEntry::SPACE) + 8 + 8 + 1 + 33 + 8
+        + 8 + (4 + MAX_DISPUTES * Dispute::SPACE) + 2 + 1 + 2
+        + 1 + (4 + MAX_ROUND_MULTIPLIERS * 2) + (1 + 4 + MAX_CLOSE_REASON_LEN) + 1
+        + (1 + 4 + MAX_OUTCOME_ACTION_LEN) + 8 + 1 + (4 + MAX_VOTE_CONDITIONS * VoteCondition::SPACE)
+        + 7 + 1 + (4 + MAX_ROUNDS as usize * RoundResult::SPACE) + 2 + 1 + 2 + 1
+        + 8 + 8 + 8 + 8 + (4 + MAX_CUSTOM_OPTIONS * (4 + 32)) + (4 + MAX_CUSTOM_OPTIONS * 4) + 2 + 1
+        + 1 + (4 + 3 * 3) + (4 + MAX_SUB_QUESTIONS * (4 + MAX_SUB_QUESTION_LEN))
+        + (4 + MAX_SUB_QUESTIONS * QuestionResult::SPACE) + 8 + 8 + 1;
+
+    /// Stable sort of `votes` by `timestamp`
+    pub fn sort_votes_by_time(&mut self) {
+        self.votes.sort_by_key(|v| v.timestamp);
+    }
+
+    /// Whether a signer may vote; an empty registry leaves the debate open
+    pub fn is_allowed(&self, key: &Pubkey) -> bool {
+        self.registered_agents.is_empty() || self.registered_agents.iter().any(|a| a.key == *key)
+    }
+
+    /// Whether `key` is the registered signer for `agent_id`
+    ///
+    /// Always true on open debates; otherwise the agent must be registered.
+    pub fn agent_key_matches(&self, agent_id: &str, key: &Pubkey) -> bool {
+        self.registered_agents.is_empty()
+            || self
+                .registered_agents
+                .iter()
+                .any(|a| a.agent_id == agent_id && a.key == *key)
+    }
+
+    /// Apply the debate's reasoning length policy: oversized reasoning is cut
+    /// to `MAX_REASONING_LEN` (on a UTF-8 boundary) when `truncate_reasoning`
+    /// is set, and rejected otherwise. Returns the text and whether it was cut.
+    pub fn fit_reasoning(&self, mut reasoning: String) -> Result<(String, bool)> {
+        if reasoning.len() <= MAX_REASONING_LEN {
+            return Ok((reasoning, false));
+        }
+        require!(
+            self.truncate_reasoning,
+            ErrorCode::ReasoningTooLong
+        );
+        let mut end = MAX_REASONING_LEN;
+        while !reasoning.is_char_boundary(end) {
+            end -= 1;
+        }
+        reasoning.truncate(end);
+        Ok((reasoning, true))
+    }
+
+    /// Registered reputation for an agent; open debates treat everyone as neutral
+    pub fn reputation_of(&self, agent_id: &str) -> u16 {
+        self.registered_agents
+            .iter()
+            .find(|a| a.agent_id == agent_id)
+            .map_or(REPUTATION_SCALE, |a| a.reputation)
+    }
+
+    /// Charge a registered agent's confidence budget for a vote moving from
+    /// `previous` to `confidence` confidence (0 for a new or withdrawn vote).
+    /// Agents outside the registry, and debates without a budget, are unlimited.
+    pub fn spend_confidence(&mut self, agent_id: &str, previous: u8, confidence: u8) -> Result<()> {
+        let budget = self.confidence_budget;
+        if budget == 0 {
+            return Ok(());
+        }
+        if let Some(agent) = self.registered_agents.iter_mut().find(|a| a.agent_id == agent_id) {
+            let spent = agent.confidence_spent.saturating_sub(previous as u16) + confidence as u16;
+            require!(
+                spent <= budget,
+                ErrorCode::ConfidenceBudgetExceeded
+            );
+            agent.confidence_spent = spent;
+        }
+        Ok(())
+    }
+
+    /// Enforce `min_vote_interval_seconds` between a registered agent's
+    /// main-question actions and record this one. The time lives on the
+    /// registration, so retracting and recasting does not restart the clock.
+    pub fn rate_limit(&mut self, agent_id: &str, now: i64) -> Result<()> {
+        let interval = self.min_vote_interval_seconds;
+        if interval == 0 {
+            return Ok(());
+        }
+        if let Some(agent) = self.registered_agents.iter_mut().find(|a| a.agent_id == agent_id) {
+            require!(
+                now.saturating_sub(agent.last_action) >= interval,
+                ErrorCode::RateLimited
+            );
+            agent.last_action = now;
+        }
+        Ok(())
+    }
+
+    /// Count votes as (decisive, abstaining)
+    ///
+    /// Abstentions show participation but never count toward the decision.
+    pub fn participation(&self) -> (u16, u16) {
+        let decisive = self.votes.iter().filter(|v| self.counts_toward_outcome(v)).count() as u16;
+        let abstaining = self
+            .votes
+            .iter()
+            .filter(|v| {
+                !v.flagged
+                    && v.on_main_question()
+                    && !self.superseded(v)
+                    && v.vote_option == VoteOption::Abstain
+            })
+            .count() as u16;
+        (decisive, abstaining)
+    }
+
+    /// Number of distinct agents that cast any vote, abstentions included
+    pub fn participant_count(&self) -> u16 {
+        let mut agents: Vec<&str> = self.votes.iter().map(|v| v.agent_id.as_str()).collect();
+        agents.sort_unstable();
+        agents.dedup();
+        agents.len() as u16
+    }
+
+    /// Share of registered agents that cast any vote, in basis points.
+    /// Open debates have no fixed electorate and report 0.
+    pub fn participation_rate(&self) -> u16 {
+        if self.registered_agents.is_empty() {
+            return 0;
+        }
+        let voted = self
+            .registered_agents
+            .iter()
+            .filter(|a| self.votes.iter().any(|v| v.agent_id == a.agent_id))
+            .count();
+        (voted * 10_000 / self.registered_agents.len()) as u16
+    }
+
+    /// Whether enough votes have been cast to decide. Abstentions count
+    /// toward `quorum` only when `abstain_counts_for_quorum` is set; flagged
+    /// votes never do.
+    pub fn meets_quorum(&self) -> bool {
+        let (decisive, abstaining) = self.participation();
+        let counted = if self.abstain_counts_for_quorum {
+            decisive + abstaining
+        } else {
+            decisive
+        };
+        counted >= self.quorum as u16
+    }
+
+    /// Follow delegations from `agent_id` to the agent that ultimately votes
+    pub fn resolve_delegate<'a>(&'a self, agent_id: &'a str) -> &'a str {
+        let mut current = agent_id;
+        // Chains are acyclic (enforced in delegate_vote), so this walk is bounded
+        for _ in 0..self.delegations.len() {
+            match self.delegations.iter().find(|d| d.from_agent == current) {
+                Some(delegation) => current = &delegation.to_agent,
+                None => break,
+            }
+        }
+        current
+    }
+
+    /// Weight multiplier for an agent: their own vote plus every delegation resolving to them
+    pub fn delegation_multiplier(&self, agent_id: &str) -> u32 {
+        let held = self
+            .delegations
+            .iter()
+            .filter(|d| self.resolve_delegate(&d.from_agent) == agent_id)
+            .count() as u32;
+        1 + held
+    }
+
+    /// Percentage a vote cast in `round` is scaled by: its entry in
+    /// `round_multipliers` when recency weighting is on, otherwise 100
+    pub fn round_multiplier(&self, round: u8) -> u16 {
+        if !self.recency_weighting {
+            return 100;
+        }
+        self.round_multipliers.get(round as usize).copied().unwrap_or(100)
+    }
+
+    /// Weight of one vote in basis points: confidence (via the tally mode),
+    /// scaled by reputation, stake relative to the average decisive stake,
+    /// delegations held and the round multiplier. The factors multiply, so a
+    /// 50-confidence vote in a 200% round weighs as much as a full-confidence
+    /// vote in a 100% round. A full-confidence vote at neutral reputation and
+    /// average stake weighs `WEIGHT_SCALE_BPS` in a 100% round.
+    pub fn vote_weight_bps(&self, vote: &Vote, decisive: u16, total_stake: u128) -> Result<u64> {
+        if total_stake == 0 {
+            return Ok(0);
+        }
+        let weight = (self.tally_mode.weight(vote.confidence) as u128 * (WEIGHT_SCALE_BPS / 100) as u128)
+            .checked_mul(vote.reputation.max(MIN_REPUTATION) as u128)
+            .and_then(|w| w.checked_mul(vote.stake_weight as u128))
+            .and_then(|w| w.checked_mul(decisive as u128))
+            .and_then(|w| w.checked_mul(self.delegation_multiplier(&vote.agent_id) as u128))
+            .and_then(|w| w.checked_mul(self.round_multiplier(vote.round) as u128))
+            .ok_or(ErrorCode::ScoreOverflow)?
+            / (REPUTATION_SCALE as u128 * total_stake * 100);
+        u64::try_from(weight).map_err(|_| ErrorCode::ScoreOverflow.into())
+    }
+
+    /// `weight` halved for every full `vote_ttl_seconds` since the vote was
+    /// cast, changed or refreshed, reaching zero after 64 periods; unchanged
+    /// when no TTL is set
+    pub fn decayed_weight(&self, vote: &Vote, weight: u64, now: i64) -> u64 {
+        if self.vote_ttl_seconds == 0 {
+            return weight;
+        }
+        let periods = now.saturating_sub(vote.last_updated).max(0) / self.vote_ttl_seconds;
+        weight.checked_shr(periods.min(64) as u32).unwrap_or(0)
+    }
+
+    /// Most a single vote may contribute, in basis points: `max_weight_bps` of
+    /// `total`, the summed unclamped decisive weight, or unlimited when the
+    /// cap is 0
+    ///
+    /// The cap applies to the final per-vote weight, after the tally mode has
+    /// transformed confidence; under `Quadratic` it clamps the square-rooted
+    /// weight, not the raw confidence.
+    pub fn weight_cap_bps(&self, total: u128) -> u64 {
+        if self.max_weight_bps == 0 {
+            return u64::MAX;
+        }
+        u64::try_from(total * self.max_weight_bps as u128 / WEIGHT_SCALE_BPS as u128).unwrap_or(u64::MAX)
+    }
+
+    /// Applied weight of every vote in basis points, parallel to `votes`,
+    /// scoring the unflagged votes `in_scope` selects as their own
+    /// electorate: stake is relative to their average decisive stake and the
+    /// weight cap to their summed weight. Other votes weigh 0.
+    pub fn scoped_weights(&self, in_scope: impl Fn(&Vote) -> bool, now: i64) -> Result<Vec<u64>> {
+        let counted = |v: &Vote| !v.flagged && in_scope(v);
+        let decisive = |v: &Vote| counted(v) && v.vote_option != VoteOption::Abstain;
+
+        // Stake is applied relative to the average decisive stake, so equal
+        // balances reproduce the plain confidence-weighted tally and scores stay
+        // on the same scale. Summing in u128 cannot overflow for 20 u64 balances.
+        let decisive_count = self.votes.iter().filter(|v| decisive(v)).count() as u16;
+        let total_stake: u128 = self
+            .votes
+            .iter()
+            .filter(|v| decisive(v))
+            .map(|v| v.stake_weight as u128)
+            .sum();
+
+        let weights = self
+            .votes
+            .iter()
+            .map(|v| {
+                if counted(v) {
+                    self.vote_weight_bps(v, decisive_count, total_stake)
+                } else {
+                    Ok(0)
+                }
+            })
+            .collect::<Result<Vec<u64>>>()?;
+        let total: u128 = self
+            .votes
+            .iter()
+            .zip(&weights)
+            .filter(|(v, _)| decisive(v))
+            .map(|(_, &w)| w as u128)
+            .sum();
+        let cap = self.weight_cap_bps(total);
+        Ok(self
+            .votes
+            .iter()
+            .zip(weights)
+            .map(|(v, w)| self.decayed_weight(v, w.min(cap), now))
+            .collect())
+    }
+
+    /// Index of the highest-scoring custom option, or None when no custom
+    /// option scored or the lead is tied
+    pub fn custom_winner(&self) -> Option<u8> {
+        let top = self.custom_scores.iter().copied().max().filter(|&s| s > 0)?;
+        let mut leaders = self.custom_scores.iter().enumerate().filter(|(_, &s)| s == top);
+        match (leaders.next(), leaders.next()) {
+            (Some((index, _)), None) => Some(index as u8),
+            _ => None,
+        }
+    }
+
+    /// Stored score for a decisive option (Abstain has no score)
+    pub fn score_of(&self, option: VoteOption) -> u32 {
+        match option {
+            VoteOption::Support => self.support_score,
+            VoteOption::Oppose => self.oppose_score,
+            VoteOption::Neutral => self.neutral_score,
+            VoteOption::Abstain => 0,
+        }
+    }
+
+    /// Score an option competes with for the outcome: the stored score, except
+    /// Neutral is discounted to `neutral_score * (100 - neutral_discount) / 100`
+    /// (rounded down). Margins and reported scores stay undiscounted.
+    pub fn outcome_score(&self, option: VoteOption) -> u32 {
+        self.discounted_score(option, self.score_of(option))
+    }
+
+    /// `score` as `option` competes with it for the outcome (see `outcome_score`)
+    pub fn discounted_score(&self, option: VoteOption, score: u32) -> u32 {
+        match option {
+            VoteOption::Neutral => (score as u64 * (100 - self.neutral_discount as u64) / 100) as u32,
+            _ => score,
+        }
+    }
+
+    /// Pick the winning option from Support/Oppose/Neutral `scores`
+    ///
+    /// Outcomes compare the integer `discounted_score`s, so options whose scaled
+    /// scores are equal are tied. A single highest score wins outright; when
+    /// two or more options share it, `tie_break` decides:
+    /// - `FavorNeutral`: Neutral
+    /// - `FavorStatusQuo`: Oppose if it is tied for first, otherwise Neutral
+    /// - `Random`: the tied option at `random_value % tied_count`, in
+    ///   Support, Oppose, Neutral order
+    /// - `AuthorityDecides`: no outcome until the authority calls `resolve_tie`
+    pub fn decide_outcome(&self, scores: [u32; 3], random_value: u64) -> Option<VoteOption> {
+        let options = [VoteOption::Support, VoteOption::Oppose, VoteOption::Neutral];
+        let competing: Vec<u32> = options
+            .iter()
+            .zip(scores)
+            .map(|(&o, s)| self.discounted_score(o, s))
+            .collect();
+        let top = competing.iter().copied().max().unwrap_or(0);
+        let tied: Vec<VoteOption> = options
+            .iter()
+            .zip(&competing)
+            .filter(|&(_, &s)| s == top)
+            .map(|(&o, _)| o)
+            .collect();
+
+        if tied.len() == 1 {
+            return Some(tied[0]);
+        }
+
+        match self.tie_break {
+            TieBreak::FavorNeutral => Some(VoteOption::Neutral),
+            TieBreak::FavorStatusQuo => {
+                if tied.contains(&VoteOption::Oppose) {
+                    Some(VoteOption::Oppose)
+                } else {
+                    Some(VoteOption::Neutral)
+                }
+            }
+            TieBreak::Random => Some(tied[(random_value % tied.len() as u64) as usize]),
+            TieBreak::AuthorityDecides => None,
+        }
+    }
+
+    /// Whether `option` holds at least `threshold_bps` of the Support/Oppose/Neutral `scores`
+    pub fn meets_threshold(&self, option: VoteOption, scores: [u32; 3]) -> bool {
+        let total: u64 = scores.iter().map(|&s| s as u64).sum();
+        let score = match option {
+            VoteOption::Support => scores[0],
+            VoteOption::Oppose => scores[1],
+            VoteOption::Neutral => scores[2],
+            VoteOption::Abstain => 0,
+        };
+        score as u64 * WEIGHT_SCALE_BPS >= self.threshold_bps as u64 * total
+    }
+
+    /// Apply the super-majority threshold to an outcome decided over `scores`,
+    /// returning the carried outcome and whether it reached consensus
+    ///
+    /// Support or Oppose only carries when it meets `threshold_bps`; short of
+    /// that the outcome falls back to Neutral without consensus.
+    pub fn carried_outcome(&self, outcome: Option<VoteOption>, scores: [u32; 3]) -> (Option<VoteOption>, bool) {
+        match outcome {
+            Some(option @ (VoteOption::Support | VoteOption::Oppose)) => {
+                if self.meets_threshold(option, scores) {
+                    (outcome, true)
+                } else {
+                    (Some(VoteOption::Neutral), false)
+                }
+            }
+            other => (other, false),
+        }
+    }
+
+    /// Store a decided outcome, applying the super-majority threshold (see
+    /// `carried_outcome`)
+    ///
+    /// A Support or Oppose win short of the threshold ends the debate without
+    /// consensus and sets `no_consensus`. `consensus_reached` is set only for a
+    /// carried Support or Oppose, so a Neutral win sets neither flag.
+    pub fn settle_outcome(&mut self, outcome: Option<VoteOption>) {
+        let decisive_win = matches!(outcome, Some(VoteOption::Support | VoteOption::Oppose));
+        let scores = [self.support_score, self.oppose_score, self.neutral_score];
+        (self.outcome, self.consensus_reached) = self.carried_outcome(outcome, scores);
+        self.no_consensus = decisive_win && !self.consensus_reached;
+        self.minority_report = self.minority_report();
+    }
+
+    /// Strongest losing position: the highest-scoring option other than the
+    /// outcome, with the index of its most confident scored vote. None while
+    /// the outcome is undecided or when no scored vote backs another option.
+    pub fn minority_report(&self) -> Option<MinorityReport> {
+        let outcome = self.outcome?;
+        let option = [VoteOption::Support, VoteOption::Oppose, VoteOption::Neutral]
+            .into_iter()
+            .filter(|&o| o != outcome)
+            .max_by_key(|&o| self.score_of(o))?;
+        // `max_by_key` keeps the last maximum, so walk backwards to prefer
+        // the earliest vote among equally confident ones
+        let vote_index = self
+            .votes
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, v)| self.counts_toward_outcome(v) && v.vote_option == option)
+            .max_by_key(|(_, v)| v.confidence)?
+            .0;
+        Some(MinorityReport {
+            option,
+            score: self.score_of(option),
+            vote_index: vote_index as u8,
+        })
+    }
+
+    /// Score the recorded votes, store the outcome and mark the debate completed
+    ///
+    /// Blocked until `tally_cooldown_seconds` have passed since the last vote
+    /// was cast or changed, so a final-second ballot gets a settle window.
+    pub fn tally(&mut self, now: i64) -> Result<()> {
+        require!(
+            now.saturating_sub(self.last_vote_timestamp) >= self.tally_cooldown_seconds,
+            ErrorCode::CooldownActive
+        );
+
+        let (decisive, abstaining) = self.participation();
+        require!(
+            self.meets_quorum(),
+            ErrorCode::QuorumNotMet
+        );
+
+        self.score(now)?;
+        self.record_round_result(now)?;
+        self.question_results = (0..self.sub_questions.len() as u8)
+            .map(|question| self.question_result(question, now))
+            .collect::<Result<_>>()?;
+        self.votes_tallied = true;
+        self.status = DebateStatus::Completed;
+        self.completion_timestamp = now;
+
+        emit!(VotesTallied {
+            debate_id: self.debate_id.clone(),
+            outcome: self.outcome,
+            support_score: self.support_score,
+            oppose_score: self.oppose_score,
+            neutral_score: self.neutral_score,
+            total_votes: self.votes.len() as u16,
+            outcome_action: self.outcome_action.clone(),
+        });
+
+        msg!(
+            "Votes tallied - Support: {}, Oppose: {}, Neutral: {}, Outcome: {:?}, Quorum: {}, Participation: {} decisive + {} abstain",
+            self.support_score,
+            self.oppose_score,
+            self.neutral_score,
+            self.outcome,
+            self.quorum,
+            decisive,
+            abstaining
+        );
+
+        Ok(())
+    }
+
+    /// Shannon entropy of the Support/Oppose/Neutral score distribution,
+    /// scaled so 0 is a unanimous council and `u16::MAX` an even three-way split
+    pub fn consensus_entropy(&self) -> u16 {
+        let scores = [self.support_score, self.oppose_score, self.neutral_score].map(u64::from);
+        let total: u64 = scores.iter().sum();
+        if total == 0 {
+            return 0;
+        }
+        // H = log2(T) - sum(s * log2(s)) / T, in 16.16 fixed point
+        let weighted: u128 = scores
+            .iter()
+            .filter(|&&s| s > 0)
+            .map(|&s| s as u128 * log2_fixed(s) as u128)
+            .sum();
+        let entropy = (log2_fixed(total) as u128 * total as u128).saturating_sub(weighted) / total as u128;
+        (entropy * u16::MAX as u128 / log2_fixed(3) as u128).min(u16::MAX as u128) as u16
+    }
+
+    /// Support/Oppose/Neutral scores of the votes `in_scope` selects,
+    /// weighted as a tally of just those votes would weight them
+    pub fn scoped_scores(&self, in_scope: impl Fn(&Vote) -> bool, now: i64) -> Result<[u32; 3]> {
+        let weights = self.scoped_weights(in_scope, now)?;
+        // Out-of-scope and flagged votes weigh 0, so every vote can be summed
+        let mut bps = [0u64; 3];
+        for (vote, weight) in self.votes.iter().zip(weights) {
+            let total = match vote.vote_option {
+                VoteOption::Support => &mut bps[0],
+                VoteOption::Oppose => &mut bps[1],
+                VoteOption::Neutral => &mut bps[2],
+                VoteOption::Abstain => continue,
+            };
+            *total = total.checked_add(weight).ok_or(ErrorCode::ScoreOverflow)?;
+        }
+        Ok(self.scale_scores(bps))
+    }
+
+    /// Summed Support/Oppose/Neutral weights in basis points as scores:
+    /// normalized when `normalized` is set, otherwise on `score_scale`
+    pub fn scale_scores(&self, bps: [u64; 3]) -> [u32; 3] {
+        if self.normalized {
+            normalize_bps(bps)
+        } else {
+            bps.map(|b| score_points(b, self.score_scale))
+        }
+    }
+
+    /// Whether the agent voted on the same question again in a later round;
+    /// only an agent's latest round is tallied
+    pub fn superseded(&self, vote: &Vote) -> bool {
+        self.votes.iter().any(|v| {
+            v.agent_id == vote.agent_id && v.question == vote.question && v.round > vote.round
+        })
+    }
+
+    /// Whether the vote is decisive and the agent's latest on the main question
+    pub fn counts_toward_outcome(&self, vote: &Vote) -> bool {
+        vote.is_decisive() && !self.superseded(vote)
+    }
+
+    /// Scores of the main-question votes cast in `round` alone
+    pub fn round_result(&self, round: u8, now: i64) -> Result<RoundResult> {
+        let [support_score, oppose_score, neutral_score] =
+            self.scoped_scores(|v| v.on_main_question() && v.round == round, now)?;
+        Ok(RoundResult {
+            round,
+            support_score,
+            oppose_score,
+            neutral_score,
+        })
+    }
+
+    /// Score the votes cast on one sub-question as their own electorate and
+    /// decide its outcome with the debate's tie-break and threshold
+    pub fn question_result(&self, question: u8, now: i64) -> Result<QuestionResult> {
+        let scores =
+            self.scoped_scores(|v| v.question == Some(question) && !self.superseded(v), now)?;
+        let decided = self.decide_outcome(scores, self.tie_break_value()?);
+        let (outcome, consensus) = self.carried_outcome(decided, scores);
+        let [support_score, oppose_score, neutral_score] = scores;
+        Ok(QuestionResult {
+            question,
+            outcome,
+            no_consensus: matches!(decided, Some(VoteOption::Support | VoteOption::Oppose)) && !consensus,
+            support_score,
+            oppose_score,
+            neutral_score,
+        })
+    }
+
+    /// Seed for `TieBreak::Random`, drawn from the current slot, which the
+    /// slot leader could predict; acceptable for settling exact ties. 0 under
+    /// every other tie-break.
+    pub fn tie_break_value(&self) -> Result<u64> {
+        if self.tie_break != TieBreak::Random {
+            return Ok(0);
+        }
+        let digest = hashv(&[self.debate_id.as_bytes(), &Clock::get()?.slot.to_le_bytes()]);
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest.to_bytes()[..8]);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Snapshot the current round, replacing any snapshot taken before the
+    /// debate was reopened
+    pub fn record_round_result(&mut self, now: i64) -> Result<()> {
+        let result = self.round_result(self.current_round, now)?;
+        self.round_results.retain(|r| r.round != result.round);
+        self.round_results.push(result);
+        Ok(())
+    }
+
+    /// Score the recorded votes and pick an outcome, storing scores, counts,
+    /// margin and outcome without finalizing the debate. Vote ages for
+    /// `vote_ttl_seconds` decay are measured against `now`.
+    pub fn score(&mut self, now: i64) -> Result<()> {
+        // Calculate weighted votes, in basis points (see `WEIGHT_SCALE_BPS`)
+        let mut support_bps: u64 = 0;
+        let mut oppose_bps: u64 = 0;
+        let mut neutral_bps: u64 = 0;
+        let mut abstain_bps: u64 = 0;
+        let mut support_count: u16 = 0;
+        let mut oppose_count: u16 = 0;
+        let mut neutral_count: u16 = 0;
+        let mut abstain_count: u16 = 0;
+        let mut confidence_buckets = [0u16; 4];
+
+        // Custom-option votes are their own electorate, so they neither dilute
+        // nor cap the main question's weights
+        let weights =
+            self.scoped_weights(|v| v.on_main_question() && !self.superseded(v), now)?;
+        let custom_weights =
+            self.scoped_weights(|v| v.custom_option.is_some() && !self.superseded(v), now)?;
+
+        let mut custom_bps = vec![0u64; self.custom_options.len()];
+        let mut applied = vec![0u64; self.votes.len()];
+        for (((vote, &weight), &custom_weight), applied) in
+            self.votes.iter().zip(&weights).zip(&custom_weights).zip(applied.iter_mut())
+        {
+            // Flagged and superseded votes stay on record but are not scored;
+            // sub-question votes are scored separately by `question_result`
+            if vote.flagged || vote.question.is_some() || self.superseded(vote) {
+                continue;
+            }
+            if let Some(index) = vote.custom_option {
+                *applied = custom_weight;
+                let total = &mut custom_bps[index as usize];
+                *total = total.checked_add(custom_weight).ok_or(ErrorCode::ScoreOverflow)?;
+                continue;
+            }
+            confidence_buckets[confidence_bucket(vote.confidence)] += 1;
+            *applied = weight;
+            let (total, count) = match vote.vote_option {
+                VoteOption::Support => (&mut support_bps, &mut support_count),
+                VoteOption::Oppose => (&mut oppose_bps, &mut oppose_count),
+                VoteOption::Neutral => (&mut neutral_bps, &mut neutral_count),
+                // Recorded for participation reporting; never scored
+                VoteOption::Abstain => (&mut abstain_bps, &mut abstain_count),
+            };
+            *total = total.checked_add(weight).ok_or(ErrorCode::ScoreOverflow)?;
+            *count += 1;
+        }
+        for (vote, weight) in self.votes.iter_mut().zip(applied) {
+            vote.applied_weight = weight;
+        }
+
+        [self.support_score, self.oppose_score, self.neutral_score] =
+            self.scale_scores([support_bps, oppose_bps, neutral_bps]);
+        self.abstain_weight = score_points(abstain_bps, self.score_scale);
+        self.custom_scores = custom_bps
+            .into_iter()
+            .map(|bps| score_points(bps, self.score_scale))
+            .collect();
+        self.consensus_entropy = self.consensus_entropy();
+        self.allocations = match self.result_mode {
+            ResultMode::WinnerTakesAll => Vec::new(),
+            ResultMode::Proportional => {
+                let shares = normalize_bps([
+                    self.support_score as u64,
+                    self.oppose_score as u64,
+                    self.neutral_score as u64,
+                ]);
+                [VoteOption::Support, VoteOption::Oppose, VoteOption::Neutral]
+                    .into_iter()
+                    .zip(shares)
+                    .map(|(option, share)| (option, share as u16))
+                    .collect()
+            }
+        };
+
+        // Margin between the two highest scores, on the same scale as the scores
+        let mut ranked_scores = [self.support_score, self.oppose_score, self.neutral_score];
+        ranked_scores.sort_unstable_by(|a, b| b.cmp(a));
+        self.winning_score = ranked_scores[0];
+        self.runner_up_score = ranked_scores[1];
+        self.margin = ranked_scores[0] - ranked_scores[1];
+        self.contested = self.margin < self.contested_threshold;
+
+        // Determine winner
+        let scores = [self.support_score, self.oppose_score, self.neutral_score];
+        let outcome = self.decide_outcome(scores, self.tie_break_value()?);
+        self.settle_outcome(outcome);
+        self.support_count = support_count;
+        self.oppose_count = oppose_count;
+        self.neutral_count = neutral_count;
+        self.abstain_count = abstain_count;
+        self.confidence_buckets = confidence_buckets;
+
+        // Mean confidence of decisive votes; 0 when every vote abstained
+        let (confidence_sum, decisive_votes) = self
+            .votes
+            .iter()
+            .filter(|v| self.counts_toward_outcome(v))
+            .fold((0u32, 0u32), |(sum, n), v| (sum + v.confidence as u32, n + 1));
+        self.avg_confidence = confidence_sum.checked_div(decisive_votes).unwrap_or(0) as u8;
+
+        // Median of the same votes; an even count averages the middle pair
+        let mut confidences: Vec<u8> = self
+            .votes
+            .iter()
+            .filter(|v| self.counts_toward_outcome(v))
+            .map(|v| v.confidence)
+            .collect();
+        confidences.sort_unstable();
+        let mid = confidences.len() / 2;
+        self.median_confidence = match confidences.len() {
+            0 => 0,
+            n if n % 2 == 1 => confidences[mid],
+            _ => ((confidences[mid - 1] as u16 + confidences[mid] as u16) / 2) as u8,
+        };
+
+        Ok(())
+    }
+
+    /// Results as reported by `get_results` and `preview_tally`
+    pub fn results(&self) -> Result<VoteResults> {
+        Ok(VoteResults {
+            debate_id: self.debate_id.clone(),
+            topic_hash: self.topic_hash,
+            outcome: self.outcome,
+            support_score: self.support_score,
+            oppose_score: self.oppose_score,
+            neutral_score: self.neutral_score,
+            total_votes: self.votes.len() as u16,
+            support_count: self.support_count,
+            oppose_count: self.oppose_count,
+            neutral_count: self.neutral_count,
+            abstain_count: self.abstain_count,
+            abstain_weight: self.abstain_weight,
+            participants: self.participant_count(),
+            participation_rate: self.participation_rate(),
+            winning_score: self.winning_score,
+            runner_up_score: self.runner_up_score,
+            margin: self.margin,
+            contested: self.contested,
+            oracle_context: self.oracle_context.clone(),
+            confidence_buckets: self.confidence_buckets,
+            consensus_reached: self.consensus_reached,
+            no_consensus: self.no_consensus,
+            avg_confidence: self.avg_confidence,
+            median_confidence: self.median_confidence,
+            minority_report: self.minority_report.map(|report| {
+                let vote = &self.votes[report.vote_index as usize];
+                MinorityReportResult {
+                    option: report.option,
+                    score: report.score,
+                    agent_id: vote.agent_id.clone(),
+                    reasoning: vote.reasoning.clone(),
+                }
+            }),
+            score_scale: self.score_scale,
+            custom_scores: self.custom_scores.clone(),
+            consensus_entropy: self.consensus_entropy,
+            normalized: self.normalized,
+            result_mode: self.result_mode,
+            allocations: self.allocations.clone(),
+        })
+    }
+}
+
+/// Total time the authority may add to a debate's deadline (7 days)
+pub const MAX_DEADLINE_EXTENSION: i64 = 7 * 24 * 60 * 60;
+
+/// Maximum number of votes a debate can hold
+pub const MAX_VOTES: usize = 20;
+
+/// Maximum votes in one `batch_cast_votes` call; three full-length ballots
+/// (~170 bytes each) plus a signature, key and profile key per agent (~128
+/// bytes) keep the transaction under its 1232-byte limit
+pub const MAX_BATCH_VOTES: usize = 3;
+
+/// Maximum votes returned by one `get_votes` call; return data is capped at 1 KiB
+pub const MAX_VOTES_PAGE: usize = (1024 - 4) / Vote::SPACE;
+
+/// Maximum number of agents in a debate's registry
+pub const MAX_REGISTERED_AGENTS: usize = 20;
+
+/// Maximum number of keys in a debate's governance set
+pub const MAX_GOVERNANCE_AUTHORITIES: usize = 5;
+
+/// Maximum number of relayers a debate can authorize
+pub const MAX_RELAYERS: usize = 5;
+
+/// Keys allowed to relay agent-signed votes into a debate. Kept beside the
+/// debate rather than in it, as the debate account is at its size limit.
+#[account]
+pub struct RelayerSet {
+    pub debate: Pubkey,                // 32 bytes
+    pub relayers: Vec<Pubkey>,         // Dynamic (MAX_RELAYERS * 32)
+}
+
+impl RelayerSet {
+    pub const INIT_SPACE: usize = 32 + (4 + MAX_RELAYERS * 32);
+}
+
+/// M-of-N control over a debate's privileged actions
+#[account]
+pub struct Governance {
+    pub debate: Pubkey,                // 32 bytes
+    pub authorities: Vec<Pubkey>,      // Dynamic (MAX_GOVERNANCE_AUTHORITIES * 32)
+    pub threshold: u8,                 // 1 byte
+    pub pending_action: Option<GovernedAction>, // 2 bytes
+    pub approvals: Vec<Pubkey>,        // Dynamic (MAX_GOVERNANCE_AUTHORITIES * 32)
+}
+
+impl Governance {
+    pub const INIT_SPACE: usize = 32 + (4 + MAX_GOVERNANCE_AUTHORITIES * 32) + 1 + 2
+        + (4 + MAX_GOVERNANCE_AUTHORITIES * 32);
+}
+
+/// Circuit breaker over every debate an authority owns. Named apart from
+/// `Governance`, which is the per-debate M-of-N approval set.
+#[account]
+pub struct AuthorityGovernance {
+    pub authority: Pubkey,             // 32 bytes
+    pub paused: bool,                  // 1 byte
+}
+
+impl AuthorityGovernance {
+    pub const INIT_SPACE: usize = 32 + 1;
+    pub const SEED: &'static [u8] = b"authority_governance";
+}
+
+/// Maximum number of debate ids an `AuthorityRegistry` retains
+pub const MAX_REGISTRY_DEBATES: usize = 64;
+
+/// Maximum debate ids returned by one `list_debates` call; return data is capped at 1 KiB
+pub const MAX_DEBATE_IDS_PAGE: usize = (1024 - 4) / (4 + MAX_DEBATE_ID_LEN);
+
+/// Ids of the debates an authority created, oldest first. Once full, each new
+/// debate drops the oldest id; `total_registered` keeps counting, so
+/// `total_registered - debate_ids.len()` ids have rolled off.
+#[account]
+pub struct AuthorityRegistry {
+    pub authority: Pubkey,             // 32 bytes
+    pub total_registered: u64,         // 8 bytes
+    pub debate_ids: Vec<String>,       // Dynamic (MAX_REGISTRY_DEBATES * (4 + MAX_DEBATE_ID_LEN))
+}
+
+impl AuthorityRegistry {
+    pub const INIT_SPACE: usize = 32 + 8 + (4 + MAX_REGISTRY_DEBATES * (4 + MAX_DEBATE_ID_LEN));
+    pub const SEED: &'static [u8] = b"authority_registry";
+
+    /// Append a debate id, rolling off the oldest when full
+    pub fn record(&mut self, debate_id: String) {
+        if self.debate_ids.len() >= MAX_REGISTRY_DEBATES {
+            self.debate_ids.remove(0);
+        }
+        self.debate_ids.push(debate_id);
+        self.total_registered = self.total_registered.saturating_add(1);
+    }
+}
+
+/// How often an agent's votes were flagged as inconsistent, across debates
+#[account]
+pub struct AgentFlags {
+    pub agent_id: String,              // 32 bytes (max)
+    pub flags: u32,                    // 4 bytes
+    pub last_evidence: [u8; 32],       // 32 bytes
+    pub last_debate: Pubkey,           // 32 bytes
+}
+
+impl AgentFlags {
+    pub const INIT_SPACE: usize = (4 + MAX_AGENT_ID_LEN) + 4 + 32 + 32;
+}
+
+/// Strongest losing position, set at tally. The reasoning is not copied;
+/// `vote_index` points at the most confident vote for `option`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MinorityReport {
+    pub option: VoteOption,            // 1 byte
+    pub score: u32,                    // 4 bytes
+    pub vote_index: u8,                // 1 byte
+}
+
+/// Combined results of two related debates (see `merge_debates`)
+#[account]
+pub struct DebateAggregate {
+    pub authority: Pubkey,             // 32 bytes
+    pub debates: [Pubkey; 2],          // 64 bytes
+    pub outcome: VoteOption,           // 1 byte
+    pub support_score: u32,            // 4 bytes
+    pub oppose_score: u32,             // 4 bytes
+    pub neutral_score: u32,            // 4 bytes
+    pub total_votes: u16,              // 2 bytes
+    pub support_count: u16,            // 2 bytes
+    pub oppose_count: u16,             // 2 bytes
+    pub neutral_count: u16,            // 2 bytes
+    pub abstain_count: u16,            // 2 bytes
+}
+
+impl DebateAggregate {
+    pub const INIT_SPACE: usize = 32 + 64 + 1 + 4 + 4 + 4 + 2 + 2 + 2 + 2 + 2;
+
+    /// Option with the strictly highest combined score, or Neutral on a tie
+    pub fn outcome(&self) -> VoteOption {
+        if self.support_score > self.oppose_score && self.support_score > self.neutral_score {
+            VoteOption::Support
+        } else if self.oppose_score > self.support_score && self.oppose_score > self.neutral_score {
+            VoteOption::Oppose
+        } else {
+            VoteOption::Neutral
+        }
+    }
+}
+
+/// Compact, immutable record of a completed debate (see `archive_debate`)
+#[account]
+pub struct DebateArchive {
+    pub debate: Pubkey,                // 32 bytes
+    pub debate_id: String,             // 4 + MAX_DEBATE_ID_LEN bytes
+    pub authority: Pubkey,             // 32 bytes
+    pub topic_hash: [u8; 32],          // 32 bytes
+    pub outcome: VoteOption,           // 1 byte
+    pub support_score: u32,            // 4 bytes
+    pub oppose_score: u32,             // 4 bytes
+    pub neutral_score: u32,            // 4 bytes
+    pub score_scale: ScoreScale,       // 1 byte
+    pub total_votes: u16,              // 2 bytes
+    pub support_count: u16,            // 2 bytes
+    pub oppose_count: u16,             // 2 bytes
+    pub neutral_count: u16,            // 2 bytes
+    pub abstain_count: u16,            // 2 bytes
+    pub timestamp: i64,                // 8 bytes
+    pub deadline: i64,                 // 8 bytes
+    pub completion_timestamp: i64,     // 8 bytes
+    pub archived_at: i64,              // 8 bytes
+}
+
+impl DebateArchive {
+    pub const INIT_SPACE: usize = 32 + (4 + MAX_DEBATE_ID_LEN) + 32 + 32 + 1 + 4 + 4 + 4 + 1
+        + 2 + 2 + 2 + 2 + 2 + 8 + 8 + 8 + 8;
+}
+
+/// Scores of the votes cast in one round, taken as the round closes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RoundResult {
+    pub round: u8,                     // 1 byte
+    pub support_score: u32,            // 4 bytes
+    pub oppose_score: u32,             // 4 bytes
+    pub neutral_score: u32,            // 4 bytes
+}
+
+impl RoundResult {
+    pub const SPACE: usize = 1 + 4 + 4 + 4;
+}
+
+/// Maximum number of sub-questions in one debate
+pub const MAX_SUB_QUESTIONS: usize = 4;
+
+/// Maximum length of a sub-question
+pub const MAX_SUB_QUESTION_LEN: usize = 64;
+
+/// Outcome and scores of one sub-question, taken at tally
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct QuestionResult {
+    pub question: u8,                  // 1 byte
+    pub outcome: Option<VoteOption>,   // 2 bytes
+    pub no_consensus: bool,            // 1 byte
+    pub support_score: u32,            // 4 bytes
+    pub oppose_score: u32,             // 4 bytes
+    pub neutral_score: u32,            // 4 bytes
+}
+
+impl QuestionResult {
+    pub const SPACE: usize = 1 + 2 + 1 + 4 + 4 + 4;
+}
+
+/// Privileged debate actions that require governance approval
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GovernedAction {
+    TallyVotes,
+    CloseDebate,
+}
+
+/// Maximum number of distinct conditions a debate's votes can depend on
+pub const MAX_VOTE_CONDITIONS: usize = 2;
+
+/// A dependency on another debate's outcome, shared by conditional votes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VoteCondition {
+    pub depends_on: Pubkey,            // 32 bytes
+    pub required_outcome: VoteOption,  // 1 byte
+}
+
+impl VoteCondition {
+    pub const SPACE: usize = 32 + 1;
+}
+
+/// A hidden vote awaiting reveal, one per agent per debate
+#[account]
+pub struct VoteCommitment {
+    pub debate: Pubkey,                // 32 bytes
+    pub agent_id: String,              // 32 bytes (max)
+    pub voter: Pubkey,                 // 32 bytes
+    pub commitment: [u8; 32],          // 32 bytes
+    pub timestamp: i64,                // 8 bytes
+    pub deposit: u64,                  // 8 bytes
+}
+
+impl VoteCommitment {
+    pub const INIT_SPACE: usize = 32 + (4 + MAX_AGENT_ID_LEN) + 32 + 32 + 8 + 8;
+}
+
+/// Maximum number of oracle entries on a debate
+pub const MAX_ORACLE_ENTRIES: usize = 4;
+
+/// Maximum length of an oracle entry key, in bytes
+pub const MAX_ORACLE_KEY_LEN: usize = 16;
+
+/// A named external value recorded alongside the votes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct OracleEntry {
+    pub key: String,                   // 16 bytes (max)
+    pub value: i64,                    // 8 bytes
+}
+
+impl OracleEntry {
+    pub const SPACE: usize = (4 + MAX_ORACLE_KEY_LEN) + 8;
+}
+
+/// Maximum length of an outcome action identifier, in bytes
+pub const MAX_OUTCOME_ACTION_LEN: usize = 32;
+
+/// Maximum length of a `close_debate` reason, in bytes
+pub const MAX_CLOSE_REASON_LEN: usize = 64;
+
+/// Maximum number of rounds a recency-weighted debate can configure
+pub const MAX_ROUND_MULTIPLIERS: usize = 10;
+
+/// Maximum number of rounds any debate can configure
+pub const MAX_ROUNDS: u8 = 10;
+
+/// Maximum number of disputes a debate can hold
+pub const MAX_DISPUTES: usize = 3;
+
+/// Maximum length of a dispute reason, in bytes
+pub const MAX_DISPUTE_REASON_LEN: usize = 48;
+
+/// An agent's challenge to a tallied outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Dispute {
+    pub agent_id: String,              // 32 bytes (max)
+    pub reason: String,                // 48 bytes (max)
+    pub timestamp: i64,                // 8 bytes
+}
+
+impl Dispute {
+    pub const SPACE: usize = (4 + MAX_AGENT_ID_LEN) + (4 + MAX_DISPUTE_REASON_LEN) + 8;
+}
+
+/// Binds an agent id to the key that must sign its votes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AgentRegistration {
+    pub agent_id: String,              // 32 bytes (max)
+    pub key: Pubkey,                   // 32 bytes
+    pub reputation: u16,               // 2 bytes (REPUTATION_SCALE = 1.0x)
+    pub confidence_spent: u16,         // 2 bytes (reset at init; see `Debate::spend_confidence`)
+    pub relay_nonce: u32,              // 4 bytes (reset at init; see `relayed_vote_message`)
+    pub last_action: i64,              // 8 bytes (reset at init; see `Debate::rate_limit`)
+}
+
+impl AgentRegistration {
+    pub const SPACE: usize = (4 + MAX_AGENT_ID_LEN) + 32 + 2 + 2 + 4 + 8;
+}
+
+/// Reputation that leaves a vote's weight unchanged. Reputation multiplies
+/// confidence as `reputation / REPUTATION_SCALE`, so 2000 doubles a vote.
+pub const REPUTATION_SCALE: u16 = 1000;
+
+/// Reputation floor applied at tally time. A reputation of 0 is treated as
+/// this floor (0.1x) rather than discarding the vote, so new or penalized
+/// agents keep a small voice.
+pub const MIN_REPUTATION: u16 = 100;
+
+/// Maximum length of a debate id, in bytes; also the PDA seed limit
+pub const MAX_DEBATE_ID_LEN: usize = 32;
+
+/// Maximum length of a debate topic, in bytes
+pub const MAX_TOPIC_LEN: usize = 128;
+
+/// Maximum length of an agent id, in bytes
+pub const MAX_AGENT_ID_LEN: usize = 32;
+
+/// Maximum length of a vote's reasoning, in bytes
+pub const MAX_REASONING_LEN: usize = 128;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Vote {
+    pub agent_id: String,              // 32 bytes (max)
     pub vote_option: VoteOption,       // 1 byte
     pub confidence: u8,                // 1 byte (0-100)
     pub reasoning: String,             // 128 bytes (max)
     pub timestamp: i64,                // 8 bytes
+    pub voter: Pubkey,                 // 32 bytes
+    pub last_updated: i64,             // 8 bytes
+    pub round: u8,                     // 1 byte
+    pub stake_weight: u64,             // 8 bytes
+    pub reputation: u16,               // 2 bytes
+    pub reasoning_truncated: bool,     // 1 byte
+    pub reasoning_is_hash: bool,       // 1 byte
+    pub condition: Option<u8>,         // 2 bytes (index into vote_conditions)
+    pub flagged: bool,                 // 1 byte
+    pub applied_weight: u64,           // 8 bytes (basis points the last tally used; 0 if unscored)
+    pub custom_option: Option<u8>,     // 2 bytes (index into custom_options)
+    pub question: Option<u8>,          // 2 bytes (index into sub_questions; None = main question)
+}
+
+impl Vote {
+    pub const SPACE: usize = (4 + MAX_AGENT_ID_LEN) + 1 + 1 + (4 + MAX_REASONING_LEN) + 8 + 32 + 8 + 1 + 8 + 2 + 1 + 1 + 2 + 1 + 8 + 2 + 2;
+
+    /// Whether the vote is on the main Support/Oppose/Neutral question rather
+    /// than a custom option or a sub-question
+    pub fn on_main_question(&self) -> bool {
+        self.custom_option.is_none() && self.question.is_none()
+    }
+
+    /// Whether the vote is scored on the main question: not an abstention
+    /// and not flagged
+    pub fn is_decisive(&self) -> bool {
+        !self.flagged && self.on_main_question() && self.vote_option != VoteOption::Abstain
+    }
+}
+
+/// Maximum number of named options on a ranked-choice debate
+pub const MAX_RANKED_OPTIONS: usize = 8;
+
+/// Maximum number of custom options a debate can define
+pub const MAX_CUSTOM_OPTIONS: usize = 8;
+
+/// Maximum number of ranked ballots a debate can hold
+pub const MAX_RANKED_BALLOTS: usize = 20;
+
+/// One agent's ranked ballot, stored beside the debate (one per agent)
+#[account]
+pub struct RankedBallot {
+    pub debate: Pubkey,                // 32 bytes
+    pub agent_id: String,              // 32 bytes (max)
+    pub voter: Pubkey,                 // 32 bytes
+    pub rankings: Vec<u8>,             // Dynamic (max 8 bytes)
+    pub timestamp: i64,                // 8 bytes
+}
+
+impl RankedBallot {
+    pub const INIT_SPACE: usize = 32 + (4 + MAX_AGENT_ID_LEN) + 32 + (4 + MAX_RANKED_OPTIONS) + 8;
+}
+
+/// Maximum number of delegations a debate can hold
+pub const MAX_DELEGATIONS: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Delegation {
+    pub from_agent: String,            // 32 bytes (max)
+    pub to_agent: String,              // 32 bytes (max)
+    pub delegator: Pubkey,             // 32 bytes
+}
+
+/// One ballot in a `batch_cast_votes` call
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VoteInput {
+    pub agent_id: String,
+    pub vote_option: VoteOption,
+    pub confidence: u8,
+    pub reasoning: String,
+}
+
+/// Tunables supplied when a debate is created
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DebateConfig {
+    pub max_rounds: u8,
+    pub voting_duration_seconds: i64,
+    pub tally_mode: TallyMode,
+    /// Named options for ranked-choice ballots (empty disables ranked voting)
+    pub ranked_options: Vec<String>,
+    /// Minimum number of votes required to tally; abstentions are included
+    /// only with `abstain_counts_for_quorum`
+    pub quorum: u8,
+    /// Token mint whose balance weights each vote (None weights all voters equally)
+    pub stake_mint: Option<Pubkey>,
+    /// How a tie for the highest score is settled
+    pub tie_break: TieBreak,
+    /// Outcomes won by a margin below this (in score units) are flagged contested
+    pub contested_threshold: u32,
+    /// Votes below this confidence are rejected
+    pub min_confidence: u8,
+    /// Agents permitted to vote and the key each must sign with (empty allows anyone)
+    pub registered_agents: Vec<AgentRegistration>,
+    /// Require votes to be committed as hashes and revealed after `begin_reveal`
+    pub commit_reveal: bool,
+    /// Hash of the full off-chain prompt the short `topic` summarises
+    pub topic_hash: Option<[u8; 32]>,
+    /// Cut oversized reasoning to fit instead of rejecting the vote
+    pub truncate_reasoning: bool,
+    /// Percentage taken off the neutral score when deciding the outcome
+    /// (0 keeps neutral at full weight; see `Debate::outcome_score`)
+    pub neutral_discount: u8,
+    /// Seconds that must pass after the last vote before the debate can be tallied
+    pub tally_cooldown_seconds: i64,
+    /// Seconds after tallying during which agents may dispute the outcome
+    pub dispute_window_seconds: i64,
+    /// Share of the non-abstain score, in basis points, Support or Oppose
+    /// needs to carry (e.g. 6667 for two-thirds; 0 for plain plurality)
+    pub threshold_bps: u16,
+    /// Largest share of the total decisive weight, in basis points, any one
+    /// vote may contribute (0 leaves weights uncapped)
+    pub max_weight_bps: u16,
+    /// Scale each vote by the multiplier of the round it was cast in
+    pub recency_weighting: bool,
+    /// Percentage per round (100 = unchanged); one entry per round when
+    /// `recency_weighting` is set, empty otherwise
+    pub round_multipliers: Vec<u16>,
+    /// Identifier of the downstream action to run once tallied, echoed in
+    /// `VotesTallied` for off-chain listeners
+    pub outcome_action: Option<String>,
+    /// Lamports the creator locks in the debate, returned by `reclaim_bond`
+    pub bond_lamports: u64,
+    /// Units for the stored scores and `contested_threshold`
+    pub score_scale: ScoreScale,
+    /// Count abstentions toward `quorum` (turnout semantics) rather than only
+    /// Support, Oppose and Neutral votes (decisiveness semantics)
+    pub abstain_counts_for_quorum: bool,
+    /// Total confidence each registered agent may spend across all rounds
+    /// (0 for unlimited); requires a non-empty `registered_agents`
+    pub confidence_budget: u16,
+    /// Scale Support/Oppose/Neutral scores to shares summing to 10 000;
+    /// requires the `BasisPoints` scale
+    pub normalized: bool,
+    /// Whether the decision is the single `outcome` or a split across options
+    pub result_mode: ResultMode,
+    /// Seconds a registered agent must wait between casting, changing,
+    /// refreshing or retracting its vote (0 for no limit); requires a
+    /// non-empty `registered_agents`
+    pub min_vote_interval_seconds: i64,
+    /// Seconds after which an unrefreshed vote's weight halves, and halves
+    /// again each further period (0 for no decay; see `refresh_vote`)
+    pub vote_ttl_seconds: i64,
+    /// Related questions voted on alongside the topic via `cast_vote_on`
+    /// (at most `MAX_SUB_QUESTIONS`, each 1-`MAX_SUB_QUESTION_LEN` bytes)
+    pub sub_questions: Vec<String>,
+    /// Lamports each commit-reveal commitment locks until it is revealed;
+    /// forfeited to the debate by `sweep_unrevealed` otherwise
+    pub reveal_deposit_lamports: u64,
+    /// Seconds after `begin_reveal` during which commitments may be opened
+    /// (0 leaves reveals open until the debate is tallied)
+    pub reveal_window_seconds: i64,
+    /// Domain-specific choices for `cast_custom_vote`, 2 to
+    /// `MAX_CUSTOM_OPTIONS` names (empty disables custom votes)
+    pub custom_options: Vec<String>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
@@ -250,21 +4848,289 @@ pub enum VoteOption {
     Abstain,
 }
 
+/// How a vote's confidence is turned into tally weight
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TallyMode {
+    /// Weight equals confidence
+    Linear,
+    /// Weight grows with the square root of confidence
+    Quadratic,
+}
+
+/// Tally weights are accumulated in basis points: a full-confidence vote at
+/// neutral reputation and average stake adds 10 000.
+pub const WEIGHT_SCALE_BPS: u64 = 10_000;
+
+/// Basis points per stored score point in `ScoreScale::Percent`, so that
+/// vote scores 100
+pub const SCORE_BPS_PER_POINT: u64 = 100;
+
+/// Units the stored scores, margins and thresholds are expressed in
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScoreScale {
+    /// A full-weight vote scores 100
+    Percent,
+    /// A full-weight vote scores 10 000, keeping the fractional weight
+    /// `Percent` rounds away
+    BasisPoints,
+}
+
+/// How a tallied debate's decision is meant to be applied
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResultMode {
+    /// The top option takes the decision, as `outcome`
+    WinnerTakesAll,
+    /// `outcome` is still set, and `allocations` splits 10 000 basis points
+    /// across Support, Oppose and Neutral by score
+    Proportional,
+}
+
+/// Index of the calibration bucket a confidence falls in:
+/// 0-25, 26-50, 51-75 and 76-100
+fn confidence_bucket(confidence: u8) -> usize {
+    match confidence {
+        0..=25 => 0,
+        26..=50 => 1,
+        51..=75 => 2,
+        _ => 3,
+    }
+}
+
+/// Convert an accumulated basis-point total to a stored score on `scale`,
+/// saturating at `u32::MAX` rather than wrapping
+fn score_points(total_bps: u64, scale: ScoreScale) -> u32 {
+    let points = match scale {
+        ScoreScale::Percent => total_bps / SCORE_BPS_PER_POINT,
+        ScoreScale::BasisPoints => total_bps,
+    };
+    u32::try_from(points).unwrap_or(u32::MAX)
+}
+
+/// Split `WEIGHT_SCALE_BPS` across Support/Oppose/Neutral in proportion to
+/// their totals, handing rounding leftovers to the largest remainders so the
+/// shares always sum exactly. A zero total (all abstained) yields all zeros.
+fn normalize_bps(totals: [u64; 3]) -> [u32; 3] {
+    let sum: u128 = totals.iter().map(|&t| t as u128).sum();
+    if sum == 0 {
+        return [0; 3];
+    }
+
+    let mut shares = [0u32; 3];
+    let mut remainders = [(0u128, 0usize); 3];
+    for (i, &total) in totals.iter().enumerate() {
+        let scaled = total as u128 * WEIGHT_SCALE_BPS as u128;
+        shares[i] = (scaled / sum) as u32;
+        remainders[i] = (scaled % sum, i);
+    }
+
+    let leftover = WEIGHT_SCALE_BPS as u32 - shares.iter().sum::<u32>();
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for &(_, i) in remainders.iter().take(leftover as usize) {
+        shares[i] += 1;
+    }
+    shares
+}
+
+/// Fixed-point factor for quadratic weights. `sqrt(confidence) * 10` maps
+/// confidence 0-100 onto the same 0-100 range linear mode uses, so the stored
+/// `support_score`/`oppose_score`/`neutral_score` are comparable across modes.
+pub const QUADRATIC_SCALE: u32 = 10;
+
+impl TallyMode {
+    /// Per-vote weight on a 0-100 scale
+    pub fn weight(&self, confidence: u8) -> u32 {
+        match self {
+            TallyMode::Linear => confidence as u32,
+            TallyMode::Quadratic => {
+                integer_sqrt(confidence as u32 * QUADRATIC_SCALE * QUADRATIC_SCALE)
+            }
+        }
+    }
+}
+
+/// Floor of the square root, computed without floating point
+fn integer_sqrt(n: u32) -> u32 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x / 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// `log2(x)` in 16.16 fixed point for `x >= 1`, computed without floating
+/// point by repeatedly squaring the mantissa
+fn log2_fixed(x: u64) -> u64 {
+    let int = 63 - x.leading_zeros() as u64;
+    // Mantissa in [1, 2) as 1.32 fixed point
+    let mut mantissa: u128 = ((x as u128) << 32) >> int;
+    let mut result = int << 16;
+    for bit in (0..16).rev() {
+        mantissa = (mantissa * mantissa) >> 32;
+        if mantissa >= 2 << 32 {
+            mantissa >>= 1;
+            result |= 1 << bit;
+        }
+    }
+    result
+}
+
+/// Policy for settling a tie for the highest score (see `Debate::decide_outcome`)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TieBreak {
+    FavorNeutral,
+    FavorStatusQuo,
+    Random,
+    AuthorityDecides,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum DebateStatus {
     Active,
     Completed,
     Closed,
+    /// Commit-reveal debates only: commitments are closed and may be opened
+    Revealing,
+    /// Tallied and past its dispute window; the outcome is locked
+    Finalized,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct VoteResults {
     pub debate_id: String,
-    pub outcome: VoteOption,
-    pub support_score: u16,
-    pub oppose_score: u16,
-    pub neutral_score: u16,
+    pub topic_hash: [u8; 32],
+    /// None while an `AuthorityDecides` tie awaits `resolve_tie`
+    pub outcome: Option<VoteOption>,
+    pub support_score: u32,
+    pub oppose_score: u32,
+    pub neutral_score: u32,
+    pub total_votes: u16,
+    pub support_count: u16,
+    pub oppose_count: u16,
+    pub neutral_count: u16,
+    pub abstain_count: u16,
+    /// Weight carried by abstentions, on the same scale as the scores
+    pub abstain_weight: u32,
+    /// Distinct agents that voted, abstentions included
+    pub participants: u16,
+    /// Basis points of registered agents that voted (0 for open debates)
+    pub participation_rate: u16,
+    /// Highest of the three option scores
+    pub winning_score: u32,
+    /// Second-highest option score
+    pub runner_up_score: u32,
+    pub margin: u32,
+    pub contested: bool,
+    /// External signals attached while the debate was open
+    pub oracle_context: Vec<OracleEntry>,
+    /// Votes per confidence range: 0-25, 26-50, 51-75, 76-100
+    pub confidence_buckets: [u16; 4],
+    /// Support or Oppose carried with at least `threshold_bps` of the score
+    pub consensus_reached: bool,
+    /// Support or Oppose led but fell short of `threshold_bps`; `outcome` is
+    /// then Neutral by fallback, not by a Neutral win
+    pub no_consensus: bool,
+    /// Mean confidence across non-abstain votes (0 if there were none)
+    pub avg_confidence: u8,
+    /// Median confidence across the same votes, less swayed by outliers
+    pub median_confidence: u8,
+    /// Strongest dissenting position, for the record
+    pub minority_report: Option<MinorityReportResult>,
+    /// Score of each custom option, parallel to the debate's `custom_options`
+    pub custom_scores: Vec<u32>,
+    /// How split the council was: 0 when every score went to one option,
+    /// `u16::MAX` for an even Support/Oppose/Neutral split
+    pub consensus_entropy: u16,
+    /// Units of every score, weight and margin above: a full-weight vote
+    /// counts 100 under `Percent` and 10 000 under `BasisPoints`
+    pub score_scale: ScoreScale,
+    /// Support/Oppose/Neutral scores are shares summing to 10 000 (all zero
+    /// when every vote abstained)
+    pub normalized: bool,
+    /// How to read the decision: `outcome` alone, or `allocations` too
+    pub result_mode: ResultMode,
+    /// Share of each option in basis points, set under `Proportional`
+    pub allocations: Vec<(VoteOption, u16)>,
+}
+
+/// The losing option with the best-argued vote for it, as reported by `get_results`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MinorityReportResult {
+    pub option: VoteOption,
+    pub score: u32,
+    pub agent_id: String,
+    pub reasoning: String,
+}
+
+/// One agent's final position relative to the tallied outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AgentOutcome {
+    pub agent_id: String,
+    pub vote_option: VoteOption,
+    pub confidence: u8,
+    pub on_winning_side: bool,
+}
+
+/// Debate metadata for clients that don't need individual votes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DebateSummary {
+    pub debate_id: String,
+    pub topic: String,
+    pub topic_hash: [u8; 32],
+    pub status: DebateStatus,
+    pub current_round: u8,
+    pub max_rounds: u8,
+    pub vote_count: u16,
+    pub votes_tallied: bool,
+    /// Why the authority closed the debate, if it was closed directly
+    pub close_reason: Option<String>,
+    /// Commit-reveal debates: commitments not yet opened
+    pub unrevealed_commits: u16,
+}
+
+#[event]
+pub struct DebateInitialized {
+    pub debate_id: String,
+    pub topic: String,
+    pub authority: Pubkey,
+    pub deadline: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub debate_id: String,
+    pub agent_id: String,
+    pub vote_option: VoteOption,
+    pub confidence: u8,
+    pub round: u8,
+}
+
+#[event]
+pub struct VotesTallied {
+    pub debate_id: String,
+    pub outcome: Option<VoteOption>,
+    pub support_score: u32,
+    pub oppose_score: u32,
+    pub neutral_score: u32,
     pub total_votes: u16,
+    pub outcome_action: Option<String>,
+}
+
+#[event]
+pub struct DeadlineExtended {
+    pub debate_id: String,
+    pub deadline: i64,
+    pub total_extension: i64,
+}
+
+#[event]
+pub struct DebateClosed {
+    pub debate_id: String,
+    pub timestamp: i64,
 }
 
 #[error_code]
@@ -279,4 +5145,689 @@ pub enum ErrorCode {
     NoVotes,
     #[msg("Votes not yet tallied")]
     VotesNotTallied,
+    #[msg("Votes have already been tallied")]
+    VotesAlreadyTallied,
+    #[msg("No vote found for this agent")]
+    VoteNotFound,
+    #[msg("Signer does not match the original voter")]
+    VoterMismatch,
+    #[msg("Debate has already reached its maximum number of rounds")]
+    MaxRoundsReached,
+    #[msg("Voting duration must be positive")]
+    InvalidVotingDuration,
+    #[msg("Voting deadline has passed")]
+    VotingClosed,
+    #[msg("Voting deadline has not been reached yet")]
+    DeadlineNotReached,
+    #[msg("Ranked options must number 2-8 with names up to 32 bytes")]
+    InvalidRankedOptions,
+    #[msg("Ranked voting is not enabled for this debate")]
+    RankedVotingDisabled,
+    #[msg("Rankings must list distinct, valid option indices")]
+    InvalidRankings,
+    #[msg("Debate cannot accept more ranked ballots")]
+    RankedBallotsFull,
+    #[msg("Not enough counted votes to meet quorum")]
+    QuorumNotMet,
+    #[msg("Stake account is required for stake-weighted debates")]
+    StakeAccountRequired,
+    #[msg("Stake account is not the voter's associated token account for the stake mint")]
+    InvalidStakeAccount,
+    #[msg("Invalid debate status for this operation")]
+    InvalidDebateStatus,
+    #[msg("Agents cannot delegate to themselves")]
+    SelfDelegation,
+    #[msg("Delegation would create a cycle")]
+    DelegationCycle,
+    #[msg("Agent has already delegated its vote")]
+    AlreadyDelegated,
+    #[msg("Debate cannot accept more delegations")]
+    DelegationsFull,
+    #[msg("Tie has not been resolved by the authority yet")]
+    TieUnresolved,
+    #[msg("Tie has already been resolved")]
+    TieAlreadyResolved,
+    #[msg("Chosen outcome is not tied for the highest score")]
+    InvalidTieResolution,
+    #[msg("Debate cannot accept more votes")]
+    DebateFull,
+    #[msg("Reasoning exceeds the maximum length")]
+    ReasoningTooLong,
+    #[msg("Agent id exceeds the maximum length")]
+    AgentIdTooLong,
+    #[msg("Offset is beyond the end of the vote list")]
+    InvalidOffset,
+    #[msg("Confidence is below the debate's minimum")]
+    ConfidenceTooLow,
+    #[msg("Agent registry exceeds the maximum number of agents or id length")]
+    InvalidAgentRegistry,
+    #[msg("Signer is not on the debate's voter allowlist")]
+    UnauthorizedVoter,
+    #[msg("Signer is not the registered key for this agent")]
+    AgentKeyMismatch,
+    #[msg("Debate requires votes to be committed and revealed")]
+    CommitRevealRequired,
+    #[msg("Commit-reveal voting is not enabled for this debate")]
+    CommitRevealDisabled,
+    #[msg("Revealed vote does not match the commitment")]
+    CommitmentMismatch,
+    #[msg("Deadline extension must be positive")]
+    InvalidDeadlineExtension,
+    #[msg("Deadline cannot be extended beyond the maximum total extension")]
+    DeadlineExtensionLimit,
+    #[msg("Vote weights overflowed the score accumulator")]
+    ScoreOverflow,
+    #[msg("Topic hash must be non-zero when provided")]
+    InvalidTopicHash,
+    #[msg("Batch must contain between 1 and MAX_BATCH_VOTES votes")]
+    InvalidBatchSize,
+    #[msg("Neutral discount must be a percentage (0-100)")]
+    InvalidNeutralDiscount,
+    #[msg("Oracle context key exceeds the maximum length")]
+    OracleKeyTooLong,
+    #[msg("Debate cannot hold more oracle context entries")]
+    OracleContextFull,
+    #[msg("Tally cooldown cannot be negative")]
+    InvalidTallyCooldown,
+    #[msg("Tally cooldown since the last vote has not elapsed")]
+    CooldownActive,
+    #[msg("Governance needs 1-5 distinct authorities and a threshold between 1 and their count")]
+    InvalidGovernance,
+    #[msg("Debate is governed; use approve_action")]
+    GovernanceRequired,
+    #[msg("Signer is not a governance authority")]
+    NotGovernanceAuthority,
+    #[msg("Authority has already approved the pending action")]
+    AlreadyApproved,
+    #[msg("A different action is already pending approval")]
+    PendingActionMismatch,
+    #[msg("No governance action is pending")]
+    NoPendingAction,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("Dispute window cannot be negative")]
+    InvalidDisputeWindow,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("Dispute reason exceeds the maximum length")]
+    DisputeReasonTooLong,
+    #[msg("Agent has already disputed this outcome")]
+    AlreadyDisputed,
+    #[msg("Debate cannot hold more disputes")]
+    DisputesFull,
+    #[msg("Consensus threshold cannot exceed 10000 basis points")]
+    InvalidThreshold,
+    #[msg("Vote weight cap cannot exceed 10000 basis points")]
+    InvalidWeightCap,
+    #[msg("A debate cannot be merged with itself")]
+    InvalidMerge,
+    #[msg("Round multipliers must match max_rounds when recency weighting is enabled")]
+    InvalidRoundMultipliers,
+    #[msg("Close reason must be non-empty and within the maximum length")]
+    InvalidCloseReason,
+    #[msg("A debate already exists for this id")]
+    DebateAlreadyExists,
+    #[msg("Debate id cannot be empty")]
+    EmptyDebateId,
+    #[msg("Debate id exceeds the maximum length")]
+    DebateIdTooLong,
+    #[msg("Topic exceeds the maximum length")]
+    TopicTooLong,
+    #[msg("Outcome action must be non-empty and within the maximum length")]
+    InvalidOutcomeAction,
+    #[msg("Bond has already been reclaimed")]
+    BondAlreadyReclaimed,
+    #[msg("Conditional votes must depend on another debate's decisive outcome")]
+    InvalidVoteCondition,
+    #[msg("Debate cannot hold more vote conditions")]
+    VoteConditionsFull,
+    #[msg("A conditional vote's dependency is missing or not yet decided")]
+    DependencyUnresolved,
+    #[msg("Vote has already been flagged")]
+    VoteAlreadyFlagged,
+    #[msg("Relayers must be 1 to 5 distinct keys")]
+    InvalidRelayers,
+    #[msg("Signer is not an authorized relayer for this debate")]
+    UnauthorizedRelayer,
+    #[msg("Missing or invalid Ed25519 verification of the agent's signature")]
+    InvalidAgentSignature,
+    #[msg("Debate outcome has not been determined")]
+    OutcomeNotDetermined,
+    #[msg("Every ranked ballot of the debate must be passed exactly once")]
+    InvalidRankedBallots,
+    #[msg("Debates can run at most 10 rounds")]
+    InvalidMaxRounds,
+    #[msg("A confidence budget requires registered agents")]
+    InvalidConfidenceBudget,
+    #[msg("Vote would exceed the agent's remaining confidence budget")]
+    ConfidenceBudgetExceeded,
+    #[msg("Reveal window cannot be negative")]
+    InvalidRevealWindow,
+    #[msg("Reveal deadline has passed")]
+    RevealWindowClosed,
+    #[msg("Commitments can still be revealed")]
+    RevealWindowOpen,
+    #[msg("Custom options must be 2 to 8 non-empty names of at most 32 bytes")]
+    InvalidCustomOptions,
+    #[msg("Option index is not one of the debate's custom options")]
+    InvalidCustomOption,
+    #[msg("Account is not the agent's council_selection profile")]
+    InvalidAgentProfile,
+    #[msg("Voting is paused for this authority's debates")]
+    GovernancePaused,
+    #[msg("Normalized scores require the BasisPoints score scale")]
+    NormalizedNeedsBasisPoints,
+    #[msg("Sub-questions must be at most 4 non-empty strings of at most 64 bytes")]
+    InvalidSubQuestions,
+    #[msg("Question index is not one of the debate's sub-questions")]
+    InvalidQuestionIndex,
+    #[msg("Minimum vote interval cannot be negative")]
+    InvalidVoteInterval,
+    #[msg("Vote was changed too recently; wait for the minimum vote interval")]
+    RateLimited,
+    #[msg("Vote TTL cannot be negative")]
+    InvalidVoteTtl,
+    #[msg("Merged vote counts overflow")]
+    CountOverflow,
+    #[msg("Each batched vote needs its agent's key as a signer")]
+    AgentSignerRequired,
+    #[msg("Agent has used every relay nonce in this debate")]
+    RelayNonceExhausted,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(agent: usize, reputation: u16, stake_weight: u64) -> Vote {
+        Vote {
+            agent_id: format!("agent-{}", agent),
+            vote_option: VoteOption::Support,
+            confidence: 100,
+            reasoning: String::new(),
+            timestamp: 0,
+            voter: Pubkey::default(),
+            last_updated: 0,
+            round: 0,
+            stake_weight,
+            reputation,
+            reasoning_truncated: false,
+            reasoning_is_hash: false,
+            condition: None,
+            flagged: false,
+            applied_weight: 0,
+            custom_option: None,
+            question: None,
+        }
+    }
+
+    fn debate(votes: Vec<Vote>) -> Debate {
+        Debate {
+            debate_id: "debate".to_string(),
+            topic: String::new(),
+            authority: Pubkey::default(),
+            max_rounds: 1,
+            current_round: 0,
+            votes,
+            timestamp: 0,
+            deadline: 0,
+            completion_timestamp: 0,
+            status: DebateStatus::Active,
+            outcome: None,
+            support_score: 0,
+            oppose_score: 0,
+            neutral_score: 0,
+            votes_tallied: false,
+            tally_mode: TallyMode::Linear,
+            ranked_options: Vec::new(),
+            ranked_ballot_count: 0,
+            elimination_order: Vec::new(),
+            ranked_winner: None,
+            quorum: 0,
+            stake_mint: None,
+            support_count: 0,
+            oppose_count: 0,
+            neutral_count: 0,
+            abstain_count: 0,
+            delegations: Vec::new(),
+            tie_break: TieBreak::FavorNeutral,
+            contested_threshold: 0,
+            margin: 0,
+            contested: false,
+            min_confidence: 0,
+            registered_agents: Vec::new(),
+            commit_reveal: false,
+            abstain_weight: 0,
+            deadline_extension: 0,
+            winning_score: 0,
+            runner_up_score: 0,
+            topic_hash: [0; 32],
+            truncate_reasoning: false,
+            neutral_discount: 0,
+            oracle_context: Vec::new(),
+            last_vote_timestamp: 0,
+            tally_cooldown_seconds: 0,
+            governed: false,
+            pending_authority: None,
+            confidence_buckets: [0; 4],
+            dispute_window_seconds: 0,
+            disputes: Vec::new(),
+            threshold_bps: 0,
+            consensus_reached: false,
+            no_consensus: false,
+            max_weight_bps: 0,
+            recency_weighting: false,
+            round_multipliers: Vec::new(),
+            close_reason: None,
+            avg_confidence: 0,
+            outcome_action: None,
+            bond_lamports: 0,
+            bond_reclaimed: false,
+            vote_conditions: Vec::new(),
+            minority_report: None,
+            score_scale: ScoreScale::Percent,
+            round_results: Vec::new(),
+            unrevealed_commits: 0,
+            abstain_counts_for_quorum: false,
+            confidence_budget: 0,
+            median_confidence: 0,
+            reveal_deposit_lamports: 0,
+            reveal_window_seconds: 0,
+            reveal_deadline: 0,
+            forfeited_lamports: 0,
+            custom_options: Vec::new(),
+            custom_scores: Vec::new(),
+            consensus_entropy: 0,
+            normalized: false,
+            result_mode: ResultMode::WinnerTakesAll,
+            allocations: Vec::new(),
+            sub_questions: Vec::new(),
+            question_results: Vec::new(),
+            min_vote_interval_seconds: 0,
+            vote_ttl_seconds: 0,
+        }
+    }
+
+    #[test]
+    fn max_confidence_votes_at_cap_tally_exactly() {
+        let mut debate = debate((0..MAX_VOTES).map(|i| vote(i, REPUTATION_SCALE, 1)).collect());
+        debate.tally(0).unwrap();
+        assert_eq!(debate.support_score, 100 * MAX_VOTES as u32);
+        assert_eq!(debate.support_count, MAX_VOTES as u16);
+        assert_eq!(debate.confidence_buckets, [0, 0, 0, MAX_VOTES as u16]);
+        assert_eq!(debate.avg_confidence, 100);
+        assert_eq!(debate.outcome, Some(VoteOption::Support));
+    }
+
+    #[test]
+    fn extreme_weights_do_not_wrap() {
+        let mut debate = debate((0..MAX_VOTES).map(|i| vote(i, u16::MAX, u64::MAX)).collect());
+        debate.tally(0).unwrap();
+        assert_eq!(debate.support_score, 131_070);
+        assert_eq!(debate.outcome, Some(VoteOption::Support));
+    }
+
+    #[test]
+    fn round_results_score_each_round_separately() {
+        let mut votes: Vec<Vote> = (0..4).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        votes[2].round = 1;
+        votes[3].round = 1;
+        votes[3].vote_option = VoteOption::Oppose;
+        let mut debate = debate(votes);
+        debate.max_rounds = 2;
+        debate.record_round_result(0).unwrap();
+        debate.current_round = 1;
+        debate.tally(0).unwrap();
+        assert_eq!(
+            debate.round_results,
+            vec![
+                RoundResult { round: 0, support_score: 200, oppose_score: 0, neutral_score: 0 },
+                RoundResult { round: 1, support_score: 100, oppose_score: 100, neutral_score: 0 },
+            ]
+        );
+        assert_eq!(debate.support_score, 300);
+    }
+
+    #[test]
+    fn rate_limit_outlives_a_retracted_vote() {
+        let mut debate = debate(Vec::new());
+        debate.min_vote_interval_seconds = 60;
+        debate.registered_agents = vec![AgentRegistration {
+            agent_id: "agent-0".to_string(),
+            key: Pubkey::new_unique(),
+            reputation: REPUTATION_SCALE,
+            confidence_spent: 0,
+            relay_nonce: 0,
+            last_action: 0,
+        }];
+        assert_eq!(debate.rate_limit("agent-0", 1_000), Ok(()));
+        // A retract at 1_000 followed by a recast is still inside the window
+        assert_eq!(debate.rate_limit("agent-0", 1_030), Err(ErrorCode::RateLimited.into()));
+        assert_eq!(debate.rate_limit("agent-0", 1_060), Ok(()));
+        assert_eq!(debate.registered_agents[0].last_action, 1_060);
+    }
+
+    #[test]
+    fn only_each_agents_latest_round_is_tallied() {
+        let mut votes: Vec<Vote> = (0..2).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        let mut recast = vote(0, REPUTATION_SCALE, 1);
+        recast.round = 1;
+        recast.vote_option = VoteOption::Oppose;
+        votes.push(recast);
+        let mut debate = debate(votes);
+        debate.score(0).unwrap();
+        assert_eq!(debate.support_score, 100);
+        assert_eq!(debate.oppose_score, 100);
+        assert_eq!(debate.participation(), (2, 0));
+        assert_eq!(debate.votes[0].applied_weight, 0);
+    }
+
+    #[test]
+    fn condorcet_winner_needs_every_pairwise_majority() {
+        let ballot = |rankings: &[u8]| RankedBallot {
+            debate: Pubkey::default(),
+            agent_id: String::new(),
+            voter: Pubkey::default(),
+            rankings: rankings.to_vec(),
+            timestamp: 0,
+        };
+        // Option 1 is nobody's first choice but beats 0 and 2 head to head
+        let ballots = [ballot(&[0, 1]), ballot(&[2, 1]), ballot(&[1])];
+        assert_eq!(condorcet_winner(3, &ballots), Some(1));
+
+        // A rock-paper-scissors cycle has no winner
+        let cycle = [ballot(&[0, 1, 2]), ballot(&[1, 2, 0]), ballot(&[2, 0, 1])];
+        assert_eq!(condorcet_winner(3, &cycle), None);
+    }
+
+    #[test]
+    fn abstentions_meet_quorum_only_when_counted() {
+        let mut votes: Vec<Vote> = (0..4).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        votes[2].vote_option = VoteOption::Abstain;
+        votes[3].vote_option = VoteOption::Abstain;
+
+        let mut strict = debate(votes.clone());
+        strict.quorum = 3;
+        assert!(!strict.meets_quorum());
+        assert_eq!(strict.tally(0), Err(ErrorCode::QuorumNotMet.into()));
+
+        let mut turnout = debate(votes);
+        turnout.quorum = 3;
+        turnout.abstain_counts_for_quorum = true;
+        assert!(turnout.meets_quorum());
+        turnout.tally(0).unwrap();
+        assert_eq!(turnout.outcome, Some(VoteOption::Support));
+        assert_eq!(turnout.abstain_count, 2);
+    }
+
+    #[test]
+    fn median_confidence_averages_the_middle_pair() {
+        let mut votes: Vec<Vote> = (0..5).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        for (vote, confidence) in votes.iter_mut().zip([10, 60, 70, 100, 90]) {
+            vote.confidence = confidence;
+        }
+        votes[4].vote_option = VoteOption::Abstain;
+        let mut debate = debate(votes);
+        debate.tally(0).unwrap();
+        assert_eq!(debate.median_confidence, 65);
+        assert_eq!(debate.avg_confidence, 60);
+    }
+
+    #[test]
+    fn custom_votes_tally_separately() {
+        let mut votes: Vec<Vote> = (0..4).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        for (vote, option) in votes.iter_mut().zip([0, 2, 2]) {
+            vote.vote_option = VoteOption::Neutral;
+            vote.custom_option = Some(option);
+        }
+        let mut debate = debate(votes);
+        debate.custom_options = vec!["Approve".into(), "Reject".into(), "Defer".into()];
+        debate.tally(0).unwrap();
+        assert_eq!(debate.custom_scores, vec![100, 0, 200]);
+        assert_eq!(debate.support_score, 100);
+        assert_eq!(debate.neutral_score, 0);
+        assert_eq!(debate.neutral_count, 0);
+        assert_eq!(debate.participation(), (1, 0));
+        assert_eq!(debate.custom_winner(), Some(2));
+    }
+
+    #[test]
+    fn consensus_entropy_spans_unanimous_to_even_split() {
+        let mut debate = debate(Vec::new());
+        debate.support_score = 500;
+        assert_eq!(debate.consensus_entropy(), 0);
+
+        debate.oppose_score = 500;
+        debate.neutral_score = 500;
+        assert!(debate.consensus_entropy() >= u16::MAX - 2);
+
+        // log2(2) / log2(3) of the range for an even two-way split
+        debate.neutral_score = 0;
+        assert!(debate.consensus_entropy().abs_diff(41_349) <= 2);
+    }
+
+    #[test]
+    fn neutral_discount_only_affects_the_outcome() {
+        let mut votes: Vec<Vote> = (0..5).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        for v in &mut votes[..3] {
+            v.vote_option = VoteOption::Neutral;
+        }
+        let mut debate = debate(votes);
+        debate.neutral_discount = 50;
+        debate.tally(0).unwrap();
+        assert_eq!(debate.neutral_score, 300);
+        assert_eq!(debate.margin, 100);
+        assert_eq!(debate.outcome, Some(VoteOption::Support));
+        assert_eq!(
+            debate.minority_report,
+            Some(MinorityReport { option: VoteOption::Neutral, score: 300, vote_index: 0 })
+        );
+    }
+
+    #[test]
+    fn super_majority_threshold_withholds_consensus() {
+        let mut votes: Vec<Vote> = (0..5).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        for v in &mut votes[..2] {
+            v.vote_option = VoteOption::Oppose;
+        }
+        let mut debate = debate(votes);
+        debate.threshold_bps = 6667;
+        debate.tally(0).unwrap();
+        assert_eq!(debate.support_score, 300);
+        assert!(!debate.consensus_reached);
+        assert!(debate.no_consensus);
+        assert_eq!(debate.outcome, Some(VoteOption::Neutral));
+
+        debate.threshold_bps = 6000;
+        debate.score(0).unwrap();
+        assert!(debate.consensus_reached);
+        assert!(!debate.no_consensus);
+        assert_eq!(debate.outcome, Some(VoteOption::Support));
+    }
+
+    #[test]
+    fn weight_cap_clamps_a_whale() {
+        let mut votes: Vec<Vote> = (0..4).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        let mut whale = vote(4, REPUTATION_SCALE, 96);
+        whale.vote_option = VoteOption::Oppose;
+        votes.push(whale);
+
+        let mut debate = debate(votes);
+        debate.score(0).unwrap();
+        assert_eq!(debate.oppose_score, 480);
+
+        // 20% of the 50 000 bps total caps the whale at 10 000 bps
+        debate.max_weight_bps = 2000;
+        debate.score(0).unwrap();
+        assert_eq!(debate.support_score, 20);
+        assert_eq!(debate.oppose_score, 100);
+        assert_eq!(debate.votes[0].applied_weight, 500);
+        assert_eq!(debate.votes[4].applied_weight, 10_000);
+    }
+
+    #[test]
+    fn recency_multiplier_scales_later_rounds() {
+        let mut votes: Vec<Vote> = (0..2).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        votes[1].vote_option = VoteOption::Oppose;
+        votes[1].round = 1;
+        let mut debate = debate(votes);
+        debate.recency_weighting = true;
+        debate.round_multipliers = vec![100, 200];
+        debate.score(0).unwrap();
+        assert_eq!(debate.support_score, 100);
+        assert_eq!(debate.oppose_score, 200);
+        assert_eq!(debate.outcome, Some(VoteOption::Oppose));
+    }
+
+    #[test]
+    fn ed25519_verifies_checks_inline_key_signature_and_message() {
+        let key = Pubkey::new_unique();
+        let signature = [7u8; 64];
+        let message = [9u8; 32];
+        let mut data = vec![1u8, 0];
+        for offset in [16u16, u16::MAX, 80, u16::MAX, 112, 32, u16::MAX] {
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        data.extend_from_slice(&signature);
+        data.extend_from_slice(key.as_ref());
+        data.extend_from_slice(&message);
+        let ix = Instruction {
+            program_id: ed25519_program::ID,
+            accounts: Vec::new(),
+            data,
+        };
+
+        assert!(ed25519_verifies(&ix, &key, &signature, &message));
+        assert!(!ed25519_verifies(&ix, &Pubkey::new_unique(), &signature, &message));
+        assert!(!ed25519_verifies(&ix, &key, &signature, &[0u8; 32]));
+        assert!(!ed25519_verifies(&Instruction { program_id: crate::ID, ..ix }, &key, &signature, &message));
+    }
+
+    #[test]
+    fn score_points_follows_scale_and_saturates() {
+        assert_eq!(score_points(10_000, ScoreScale::Percent), 100);
+        assert_eq!(score_points(10_050, ScoreScale::BasisPoints), 10_050);
+        assert_eq!(score_points(u64::MAX, ScoreScale::Percent), u32::MAX);
+        assert_eq!(score_points(u64::MAX, ScoreScale::BasisPoints), u32::MAX);
+    }
+
+    #[test]
+    fn stale_votes_halve_per_ttl_period() {
+        let mut votes: Vec<Vote> = (0..3).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        votes[1].last_updated = 100;
+        votes[2].last_updated = 250;
+        let mut debate = debate(votes);
+        debate.vote_ttl_seconds = 100;
+        debate.score(300).unwrap();
+
+        let applied: Vec<u64> = debate.votes.iter().map(|v| v.applied_weight).collect();
+        assert_eq!(applied, [1_250, 2_500, 10_000]);
+        assert_eq!(debate.decayed_weight(&debate.votes[0], 10_000, i64::MAX), 0);
+    }
+
+    #[test]
+    fn authority_registry_rolls_off_oldest_ids() {
+        let mut registry = AuthorityRegistry {
+            authority: Pubkey::default(),
+            total_registered: 0,
+            debate_ids: Vec::new(),
+        };
+        for i in 0..MAX_REGISTRY_DEBATES + 2 {
+            registry.record(format!("debate-{}", i));
+        }
+        assert_eq!(registry.debate_ids.len(), MAX_REGISTRY_DEBATES);
+        assert_eq!(registry.debate_ids[0], "debate-2");
+        assert_eq!(registry.total_registered, MAX_REGISTRY_DEBATES as u64 + 2);
+    }
+
+    #[test]
+    fn sub_question_votes_tally_separately() {
+        let mut votes: Vec<Vote> = (0..4).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        votes[1].question = Some(0);
+        votes[1].vote_option = VoteOption::Oppose;
+        votes[2].question = Some(0);
+        votes[2].vote_option = VoteOption::Oppose;
+        votes[3].question = Some(1);
+        let mut debate = debate(votes);
+        debate.sub_questions = vec!["Scope".into(), "Budget".into()];
+        debate.tally(0).unwrap();
+
+        assert_eq!((debate.support_score, debate.oppose_score, debate.support_count), (100, 0, 1));
+        assert_eq!(debate.question_result(0, 0).unwrap(), debate.question_results[0]);
+        assert_eq!(
+            debate.question_results,
+            vec![
+                QuestionResult { question: 0, outcome: Some(VoteOption::Oppose), no_consensus: false, support_score: 0, oppose_score: 200, neutral_score: 0 },
+                QuestionResult { question: 1, outcome: Some(VoteOption::Support), no_consensus: false, support_score: 100, oppose_score: 0, neutral_score: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn sorting_votes_by_time_is_stable() {
+        let mut votes: Vec<Vote> = (0..4).map(|i| vote(i, REPUTATION_SCALE, 1)).collect();
+        for (vote, timestamp) in votes.iter_mut().zip([30, 10, 30, 20]) {
+            vote.timestamp = timestamp;
+        }
+        let mut debate = debate(votes);
+        debate.sort_votes_by_time();
+        let order: Vec<&str> = debate.votes.iter().map(|v| v.agent_id.as_str()).collect();
+        assert_eq!(order, ["agent-1", "agent-3", "agent-0", "agent-2"]);
+    }
+
+    #[test]
+    fn normalized_scores_sum_to_full_scale() {
+        assert_eq!(normalize_bps([1, 1, 1]), [3_334, 3_333, 3_333]);
+        assert_eq!(normalize_bps([u64::MAX, u64::MAX, 0]), [5_000, 5_000, 0]);
+        assert_eq!(normalize_bps([0, 0, 0]), [0, 0, 0]);
+
+        let mut dissent = vote(2, REPUTATION_SCALE, 1);
+        dissent.vote_option = VoteOption::Oppose;
+        let mut debate = debate(vec![vote(0, REPUTATION_SCALE, 1), vote(1, REPUTATION_SCALE, 1), dissent]);
+        debate.score_scale = ScoreScale::BasisPoints;
+        debate.normalized = true;
+        debate.tally(0).unwrap();
+        assert_eq!([debate.support_score, debate.oppose_score, debate.neutral_score], [6_667, 3_333, 0]);
+        assert_eq!(debate.outcome, Some(VoteOption::Support));
+        assert!(debate.allocations.is_empty());
+
+        debate.result_mode = ResultMode::Proportional;
+        debate.tally(0).unwrap();
+        assert_eq!(
+            debate.allocations,
+            vec![(VoteOption::Support, 6_667), (VoteOption::Oppose, 3_333), (VoteOption::Neutral, 0)]
+        );
+    }
+
+    #[test]
+    fn agent_profiles_bind_the_voter_and_cap_reputation() {
+        let voter = Pubkey::new_unique();
+        let mut profile = AgentProfile {
+            agent_id: "agent-0".into(),
+            pubkey: voter,
+            category: 0,
+            reputation: u16::MAX,
+            last_served: 0,
+        };
+        assert_eq!(profile_reputation(&profile, &voter), Ok(MAX_REPUTATION));
+        profile.reputation = 1500;
+        assert_eq!(profile_reputation(&profile, &voter), Ok(1500));
+        assert_eq!(
+            profile_reputation(&profile, &Pubkey::new_unique()),
+            Err(ErrorCode::AgentKeyMismatch.into())
+        );
+
+        // A profile PDA the council program never created is rejected
+        let key = agent_profile_address("agent-0");
+        let owner = system_program::ID;
+        let (mut lamports, mut data) = (0u64, Vec::new());
+        let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        let mut debate = debate(vec![vote(0, REPUTATION_SCALE, 1)]);
+        assert_eq!(
+            apply_agent_profile(&mut debate, &account, &voter),
+            Err(ErrorCode::InvalidAgentProfile.into())
+        );
+    }
 }